@@ -7,6 +7,7 @@ use derive_new::new;
 use rayon::prelude::*;
 
 use advent_of_code_2023::{execute, Type};
+use advent_of_code_2023::matrix::{Direction, Index, Size};
 
 type Input = PipeMap;
 type Input2 = Input;
@@ -323,29 +324,9 @@ impl PipeIndex {
     }
 
     fn move_direction(&self, direction: Direction) -> Option<PipeIndex> {
-        match direction {
-            Direction::North => {
-                if self.y > 0 {
-                    return Some(self.new_xy(self.x, self.y - 1));
-                }
-            }
-            Direction::South => {
-                if self.y < self.height - 1 {
-                    return Some(self.new_xy(self.x, self.y + 1));
-                }
-            }
-            Direction::West => {
-                if self.x > 0 {
-                    return Some(self.new_xy(self.x - 1, self.y));
-                }
-            }
-            Direction::East => {
-                if self.x < self.width - 1 {
-                    return Some(self.new_xy(self.x + 1, self.y));
-                }
-            }
-        }
-        None
+        Index::new(self.x, self.y)
+            .step_within(direction, Size::new(self.width, self.height))
+            .map(|index| self.new_xy(index.x(), index.y()))
     }
 
     fn x_range(&self) -> Range<usize> {
@@ -440,26 +421,6 @@ impl Iterator for PipeMapIter<'_> {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
-
-impl Direction {
-    const ALL: [Direction; 4] = [Direction::North, Direction::South, Direction::West, Direction::East];
-    fn opposite(&self) -> Self {
-        match self {
-            Direction::North => Direction::South,
-            Direction::South => Direction::North,
-            Direction::West => Direction::East,
-            Direction::East => Direction::West,
-        }
-    }
-}
-
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 struct Connection {
     sides: [Direction; 2],
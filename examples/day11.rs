@@ -9,6 +9,8 @@ use num::{PrimInt, Unsigned};
 use rayon::prelude::*;
 
 use advent_of_code_2023::{execute, Type};
+use advent_of_code_2023::cell::FromChar;
+use advent_of_code_2023::matrix::{Index, Offset};
 
 type Input = Vec<Vec<Cell>>;
 type Input2 = Input;
@@ -50,31 +52,26 @@ fn expand(input: &Input, space_multiplier: u64) -> Vec<Galaxy> {
                 Cell::Galaxy => {
                     has_galaxy = true;
                     columns_with_galaxies.insert(x);
-                    galaxies.push(Galaxy::new(galaxies.len() as u32 + 1, Location::new(x as u64, y as u64)));
+                    galaxies.push(Galaxy::new(galaxies.len() as u32 + 1, Index::new(x, y)));
                 }
                 Cell::Space => (),
             }
         }
         if !has_galaxy {
-            expand_y.push(y as u64);
+            expand_y.push(y);
         }
     }
     let expand_x = (0..input.len()).into_iter()
         .filter(|i| !columns_with_galaxies.contains(i))
-        .map(|v| v as u64)
         .collect::<Vec<_>>();
 
     // -1 as we already have x1 expansion by default
-    let space_multiplier = space_multiplier - 1;
+    let space_multiplier = space_multiplier as isize - 1;
     for galaxy in galaxies.iter_mut() {
         let index = galaxy.index;
-        let expand_x_times = expand_x.iter().take_while(|v| **v < index.x).count() as u64;
-        let expand_y_times = expand_y.iter().take_while(|v| **v < index.y).count() as u64;
-        let index = Location::new(
-            index.x + expand_x_times * space_multiplier,
-            index.y + expand_y_times * space_multiplier,
-        );
-        galaxy.index = index;
+        let expand_x_times = expand_x.iter().take_while(|v| **v < index.x()).count() as isize;
+        let expand_y_times = expand_y.iter().take_while(|v| **v < index.y()).count() as isize;
+        galaxy.index += Offset::new(expand_x_times, expand_y_times) * space_multiplier;
     }
     galaxies
 }
@@ -108,7 +105,7 @@ impl Display for GalaxyDistance {
 #[derive(Debug, Copy, Clone, new, Eq, PartialEq)]
 struct Galaxy {
     id: u32,
-    index: Location,
+    index: Index,
 }
 
 impl Display for Galaxy {
@@ -119,34 +116,15 @@ impl Display for Galaxy {
 
 impl Galaxy {
     fn distance(&self, other: &Galaxy) -> u64 {
-        self.index.distance(&other.index)
+        self.index.manhattan(other.index) as u64
     }
 }
 
-
-#[derive(Debug, Copy, Clone, new, Eq, PartialEq)]
-struct Location {
-    x: u64,
-    y: u64,
-}
-
-impl Display for Location {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
-}
-
-impl Location {
-    fn distance(&self, other: &Location) -> u64 {
-        let dx = (self.x as i64 - other.x as i64).abs() as u64;
-        let dy = (self.y as i64 - other.y as i64).abs() as u64;
-        dx + dy
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, FromChar)]
 enum Cell {
+    #[ch = '#']
     Galaxy,
+    #[ch = '.']
     Space,
 }
 
@@ -154,24 +132,15 @@ mod parser {
     use std::str::FromStr;
 
     use nom::{IResult, Parser};
-    use nom::branch::alt;
-    use nom::bytes::complete::tag;
     use nom::character::complete::line_ending;
-    use nom::combinator::{opt, value};
+    use nom::combinator::opt;
     use nom::multi::many1;
     use nom::sequence::terminated;
 
     use crate::{Cell, Input, Input2};
 
     pub(crate) fn parse(input: &str) -> IResult<&str, Input> {
-        many1(terminated(many1(cell), opt(line_ending)))(input)
-    }
-
-    fn cell(input: &str) -> IResult<&str, Cell> {
-        alt((
-            value(Cell::Galaxy, tag("#")),
-            value(Cell::Space, tag(".")),
-        ))(input)
+        many1(terminated(many1(Cell::parse), opt(line_ending)))(input)
     }
 
     pub(crate) fn parse2(input: &str) -> IResult<&str, Input2> {
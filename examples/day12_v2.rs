@@ -8,6 +8,7 @@ use memoize::memoize;
 use rayon::prelude::*;
 
 use advent_of_code_2023::{execute, Type};
+use advent_of_code_2023::cell::FromChar;
 
 type Input = Vec<(Vec<SpringStatus>, Vec<usize>)>;
 type Input2 = Input;
@@ -123,10 +124,13 @@ fn next_is_not_damaged(status_line: StatusLine, groups: Groups) -> bool {
     status_line[groups[0]] != SpringStatus::Damaged
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, FromChar)]
 enum SpringStatus {
+    #[ch = '.']
     Operational,
+    #[ch = '#']
     Damaged,
+    #[ch = '?']
     Unknown,
 }
 
@@ -258,11 +262,10 @@ mod tests {
 
 
 mod parser {
-    use nom::branch::alt;
     use nom::bytes::complete::tag;
     use nom::character::complete;
     use nom::character::complete::line_ending;
-    use nom::combinator::{map, opt, value};
+    use nom::combinator::{map, opt};
     use nom::IResult;
     use nom::multi::{many1, separated_list1};
     use nom::sequence::{separated_pair, terminated};
@@ -274,15 +277,7 @@ mod parser {
     }
 
     pub(crate) fn parse_line(input: &str) -> IResult<&str, (Vec<SpringStatus>, Vec<usize>)> {
-        terminated(separated_pair(many1(status), tag(" "), damaged_groups), opt(line_ending))(input)
-    }
-
-    fn status(input: &str) -> IResult<&str, SpringStatus> {
-        alt((
-            value(SpringStatus::Operational, tag(".")),
-            value(SpringStatus::Damaged, tag("#")),
-            value(SpringStatus::Unknown, tag("?")),
-        ))(input)
+        terminated(separated_pair(many1(SpringStatus::parse), tag(" "), damaged_groups), opt(line_ending))(input)
     }
 
     fn damaged_groups(input: &str) -> IResult<&str, Vec<usize>> {
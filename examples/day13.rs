@@ -7,6 +7,7 @@ use tracing::{debug, trace};
 use tracing::metadata::LevelFilter;
 
 use advent_of_code_2023::{execute, tracing, Type};
+use advent_of_code_2023::cell::{FromChar, ToChar};
 use advent_of_code_2023::matrix::Matrix;
 
 type Input = Vec<Matrix<Symbol>>;
@@ -160,18 +161,24 @@ impl Mirror {
 }
 
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, FromChar, ToChar)]
 enum Symbol {
+    #[ch = '.']
     Ash,
+    #[ch = '#']
     Rock,
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
     use rstest::rstest;
     use tracing_test::traced_test;
 
-    use crate::{find_mirror, parser};
+    use advent_of_code_2023::matrix::Matrix;
+    use advent_of_code_2023::testing::{check_round_trip, grids};
+
+    use crate::{find_mirror, parser, Symbol};
 
     #[traced_test]
     #[rstest]
@@ -224,28 +231,30 @@ mod tests {
         let actual = find_mirror(&output, 1).iter().map(|v| v.sum()).sum();
         assert_eq!(expected, actual, "wrong answer");
     }
+
+    #[test]
+    fn test_pattern_round_trip() {
+        let render = |matrix: &Matrix<Symbol>| matrix.rows()
+            .map(|row| row.iter().map(|symbol| symbol.to_string()).join(""))
+            .join("\n");
+        check_round_trip(&grids(1..18, &Symbol::ALL), render, parser::pattern);
+    }
 }
 
 mod parser {
-    use itertools::Itertools;
-    use nom::branch::alt;
-    use nom::bytes::complete::tag;
     use nom::character::complete::line_ending;
-    use nom::combinator::{opt, value};
+    use nom::combinator::opt;
     use nom::IResult;
-    use nom::multi::{many1, separated_list1};
+    use nom::multi::many1;
     use nom::sequence::terminated;
 
+    use advent_of_code_2023::blocks;
     use advent_of_code_2023::matrix::Matrix;
 
     use crate::{Input, Input2, Symbol};
 
     pub(crate) fn parse(input: &str) -> IResult<&str, Input> {
-        let (input, dataset) = separated_list1(line_ending, pattern)(input)?;
-        let output = dataset.into_iter()
-            .map(|pattern| Matrix::from(pattern))
-            .collect_vec();
-        Ok((input, output))
+        blocks(pattern)(input)
     }
 
     pub(crate) fn parse2(input: &str) -> IResult<&str, Input2> {
@@ -253,17 +262,10 @@ mod parser {
     }
 
     pub(crate) fn pattern(input: &str) -> IResult<&str, Matrix<Symbol>> {
-        let line = terminated(many1(symbol), opt(line_ending));
+        let line = terminated(many1(Symbol::parse), opt(line_ending));
         let (input, output) = many1(line)(input)?;
         Ok((input, output.into()))
     }
-
-    fn symbol(input: &str) -> IResult<&str, Symbol> {
-        alt((
-            value(Symbol::Ash, tag(".")),
-            value(Symbol::Rock, tag("#")),
-        ))(input)
-    }
 }
 
 
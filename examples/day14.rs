@@ -1,15 +1,14 @@
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
-use std::ops::Index;
 use std::rc::Rc;
 use std::str::FromStr;
 
 use rayon::prelude::*;
-use tracing::{debug, info};
+use tracing::{debug, info, trace};
 use tracing::metadata::LevelFilter;
 
 use advent_of_code_2023::{execute, tracing, Type};
-use advent_of_code_2023::matrix::Matrix;
+use advent_of_code_2023::matrix::{Direction, Fingerprint, Index, Matrix};
 
 type Input = Grid;
 type Input2 = Input;
@@ -27,6 +26,7 @@ fn main() {
 
 fn solve1(input: &Input) -> Output {
     let tilted = tilted_north(&input);
+    trace!("Tilted north:\n{}", tilted.render(&render_cell));
     let actual = calculate_north_weight(&tilted);
     actual
 }
@@ -36,25 +36,26 @@ const ITER_SIZE: usize = 1_000_000_000;
 #[derive(Debug, Clone, Eq, PartialEq)]
 struct HashedMatrix {
     grid: Grid,
-    hash: usize,
+    fingerprint: Fingerprint,
+    weight: usize,
 }
 
 impl HashedMatrix {
-    fn new(grid: Grid) -> Self {
-        let hash = calculate_north_weight(&grid);
-        Self { grid, hash }
+    fn new(grid: Grid, fingerprint: Fingerprint) -> Self {
+        let weight = calculate_north_weight(&grid);
+        Self { grid, fingerprint, weight }
     }
 }
 
 impl Hash for HashedMatrix {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write_usize(self.hash)
+        self.fingerprint.hash(state)
     }
 }
 
 fn solve2(input: &Input2) -> Output {
     let mut cache = HashMap::<Rc<HashedMatrix>, Rc<HashedMatrix>>::new();
-    let mut current = Rc::new(HashedMatrix::new(input.clone()));
+    let mut current = Rc::new(HashedMatrix::new(input.clone(), input.fingerprint()));
     let mut contiguous_hits = 0;
     let mut last_iteration = 0_usize;
 
@@ -64,8 +65,10 @@ fn solve2(input: &Input2) -> Output {
             contiguous_hits += 1;
         } else {
             contiguous_hits = 0;
-            let result = cycle(&current.grid);
-            cache.insert(current.clone(), Rc::new(HashedMatrix::new(result)));
+            let mut grid = current.grid.clone();
+            let mut fingerprint = current.fingerprint;
+            cycle_tracked(&mut grid, &mut fingerprint);
+            cache.insert(current.clone(), Rc::new(HashedMatrix::new(grid, fingerprint)));
         }
         if contiguous_hits > 2 {
             last_iteration = iteration;
@@ -76,7 +79,7 @@ fn solve2(input: &Input2) -> Output {
     let loop_vec = detect_loop(&cache, current.clone());
     debug!("Loop description:");
     for (i, mx) in loop_vec.iter().enumerate() {
-        debug!("{}. weight = {}", i + 1, mx.hash);
+        debug!("{}. weight = {}", i + 1, mx.weight);
     }
     let remaining_iterations = ITER_SIZE - last_iteration;
     let loop_index = remaining_iterations % loop_vec.len();
@@ -84,7 +87,7 @@ fn solve2(input: &Input2) -> Output {
     debug!("remaining loops: {}", remaining_iterations / loop_vec.len());
     debug!("use loop index: {}", loop_index + 1);
     let target = loop_vec[loop_index].clone();
-    return target.hash;
+    return target.weight;
 }
 
 fn detect_loop(map: &HashMap<Rc<HashedMatrix>, Rc<HashedMatrix>>, from: Rc<HashedMatrix>) -> Vec<Rc<HashedMatrix>> {
@@ -98,128 +101,56 @@ fn detect_loop(map: &HashMap<Rc<HashedMatrix>, Rc<HashedMatrix>>, from: Rc<Hashe
     vec
 }
 
-fn cycle(input: &Grid) -> Grid {
-    let input = tilted_north(input);
-    let input = tilted_west(&input);
-    let input = tilted_south(&input);
-    let input = tilted_east(&input);
-    input
-}
-
-fn tilted_north(input: &Grid) -> Grid {
-    let mut target: Matrix<Cell> = Matrix::new(input.size());
-    for column in input.columns() {
-        let x = column.x();
-        let mut next_to_move = 0_usize;
-        for y in 0..column.len() {
-            let cell = column[y];
-            match cell {
-                None => continue,
-                Some(Rock::Cube) => {
-                    next_to_move = y + 1;
-                    target.set_at(x, y, cell);
-                }
-                Some(Rock::Round) => {
-                    target.set_at(x, next_to_move, cell);
-                    next_to_move += 1;
-                }
-            }
-        }
+/// Tilts north, west, south and east, `fingerprint` follows every moved rock.
+fn cycle_tracked(grid: &mut Grid, fingerprint: &mut Fingerprint) {
+    for direction in [Direction::North, Direction::West, Direction::South, Direction::East] {
+        tilt(grid, direction, fingerprint);
     }
-    target
 }
 
-fn tilted_south(input: &Grid) -> Grid {
-    let mut target: Matrix<Cell> = Matrix::new(input.size());
-    for column in input.columns() {
-        let x = column.x();
-        let mut next_to_move = column.len() - 1;
-        for y in (0..column.len()).rev() {
-            let cell = column[y];
-            match cell {
-                None => continue,
-                Some(Rock::Cube) => {
-                    target.set_at(x, y, cell);
-                    if y == 0 {
-                        break;
-                    }
-                    next_to_move = y - 1;
-                }
-                Some(Rock::Round) => {
-                    target.set_at(x, next_to_move, cell);
-                    if next_to_move == 0 {
-                        break;
-                    }
-                    next_to_move -= 1;
-                }
-            }
-        }
-    }
+fn tilted_north(input: &Grid) -> Grid {
+    let mut target = input.clone();
+    let mut fingerprint = target.fingerprint();
+    tilt(&mut target, Direction::North, &mut fingerprint);
     target
 }
 
-fn tilted_west(input: &Grid) -> Grid {
-    let mut target: Matrix<Cell> = Matrix::new(input.size());
-    for row in input.rows() {
-        let y = row.y();
+/// Rolls round rocks towards `direction` until they hit a cube rock, another round rock or the edge.
+fn tilt(grid: &mut Grid, direction: Direction, fingerprint: &mut Fingerprint) {
+    let (width, height) = (grid.size().width(), grid.size().height());
+    // Lanes start at the edge the rocks roll to.
+    let edge: Vec<Index> = match direction {
+        Direction::North => (0..width).map(|x| Index::new(x, 0)).collect(),
+        Direction::South => (0..width).map(|x| Index::new(x, height - 1)).collect(),
+        Direction::West => (0..height).map(|y| Index::new(0, y)).collect(),
+        Direction::East => (0..height).map(|y| Index::new(width - 1, y)).collect(),
+    };
+    let lanes = edge.into_iter()
+        .map(|start| std::iter::once(start).chain(grid.ray(start, direction.opposite())).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    for lane in lanes {
         let mut next_to_move = 0_usize;
-        for x in 0..row.len() {
-            let cell = row[x];
-            match cell {
+        for (i, &index) in lane.iter().enumerate() {
+            match grid.get(index) {
                 None => continue,
-                Some(Rock::Cube) => {
-                    next_to_move = x + 1;
-                    target.set_at(x, y, cell);
-                }
+                Some(Rock::Cube) => next_to_move = i + 1,
                 Some(Rock::Round) => {
-                    target.set_at(next_to_move, y, cell);
-                    next_to_move += 1;
-                }
-            }
-        }
-    }
-    target
-}
-
-fn tilted_east(input: &Grid) -> Grid {
-    let mut target: Matrix<Cell> = Matrix::new(input.size());
-    for row in input.rows() {
-        let y = row.y();
-        let mut next_to_move = row.len() - 1;
-        for x in (0..row.len()).rev() {
-            let cell = row[x];
-            match cell {
-                None => continue,
-                Some(Rock::Cube) => {
-                    target.set_at(x, y, cell);
-                    if x == 0 {
-                        break;
-                    }
-                    next_to_move = x - 1;
-                }
-                Some(Rock::Round) => {
-                    target.set_at(next_to_move, y, cell);
-                    if next_to_move == 0 {
-                        break;
+                    if i != next_to_move {
+                        grid.set_tracked(lane[next_to_move], Some(Rock::Round), fingerprint);
+                        grid.set_tracked(index, None, fingerprint);
                     }
-                    next_to_move -= 1;
+                    next_to_move += 1;
                 }
             }
         }
     }
-    target
 }
 
 fn calculate_north_weight(input: &Grid) -> usize {
-    let mut total = 0_usize;
-    for column in input.columns() {
-        for (index, cell) in column.iter().enumerate() {
-            if let Some(Rock::Round) = cell {
-                total += column.len() - index;
-            }
-        }
-    }
-    total
+    let height = input.size().height();
+    input.positions(|cell| *cell == Some(Rock::Round))
+        .map(|index| height - index.y())
+        .sum()
 }
 
 
@@ -230,13 +161,28 @@ enum Rock {
 }
 
 type Cell = Option<Rock>;
+
+fn render_cell(cell: &Cell) -> String {
+    match cell {
+        None => ".",
+        Some(Rock::Cube) => "#",
+        Some(Rock::Round) => "O",
+    }.to_string()
+}
 type Grid = Matrix<Cell>;
 
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
 
-    use crate::{calculate_north_weight, cycle, parser, tilted_north};
+    use crate::{calculate_north_weight, cycle_tracked, Grid, parser, tilted_north};
+
+    fn cycle(input: &Grid) -> Grid {
+        let mut grid = input.clone();
+        let mut fingerprint = grid.fingerprint();
+        cycle_tracked(&mut grid, &mut fingerprint);
+        grid
+    }
 
     #[rstest]
     #[case("O....#....
@@ -369,6 +315,16 @@ O.#..O.#.#
         let (_, tilted_expected) = parser::parse(expected_output).unwrap();
         assert_eq!(tilted_expected, tilted_actual, "transformation incorrect");
     }
+
+    #[test]
+    fn check_tracked_fingerprint() {
+        let (_, mut grid) = parser::parse(include_str!("day14/demo")).unwrap();
+        let mut fingerprint = grid.fingerprint();
+        for _ in 0..5 {
+            cycle_tracked(&mut grid, &mut fingerprint);
+            assert_eq!(grid.fingerprint(), fingerprint);
+        }
+    }
 }
 
 
@@ -74,9 +74,9 @@ mod parser {
     use nom::combinator::{map, opt};
     use nom::error::context;
     use nom::multi::{many1, separated_list0, separated_list1};
-    use nom::sequence::{delimited, separated_pair, terminated};
+    use nom::sequence::{separated_pair, terminated};
 
-    use advent_of_code_2023::{decimal, decimal_legacy};
+    use advent_of_code_2023::{decimal, labeled_id};
 
     use crate::{CubeSet, Game};
 
@@ -86,7 +86,7 @@ mod parser {
     }
 
     fn parse_game(input: &str) -> IResult<&str, Game> {
-        let (input, number) = delimited(tag("Game "), decimal, tag(": "))(input)?;
+        let (input, number) = labeled_id("Game")(input)?;
         let (input, list) = context("parse_game", separated_list0(tag("; "), parse_set))(input)?;
         let game = Game::from_sets(number, list);
         Ok((input, game))
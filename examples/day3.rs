@@ -1,11 +1,11 @@
 use std::collections::HashSet;
 
-use advent_of_code_2023::{execute, Type};
+use advent_of_code_2023::{execute_located, Type};
 
 fn main() {
-    execute(3, Type::Demo, parser::parse, |values| solve1(values));
-    execute(3, Type::Task1, parser::parse, |values| solve1(values));
-    execute(3, Type::Task2, parser::parse, |values| solve2(values));
+    execute_located(3, Type::Demo, parser::parse, |values| solve1(values));
+    execute_located(3, Type::Task1, parser::parse, |values| solve1(values));
+    execute_located(3, Type::Task2, parser::parse, |values| solve2(values));
 }
 
 fn solve1(engine: &Engine) -> u32 {
@@ -120,35 +120,29 @@ impl Number {
 mod parser {
     use nom::{IResult, Parser};
     use nom::branch::alt;
-    use nom::bytes::complete::{tag, take};
-    use nom::character::complete::{digit1, line_ending};
-    use nom::combinator::{map, map_res, opt, verify};
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{line_ending, satisfy};
+    use nom::combinator::{map, opt, value};
     use nom::error::context;
     use nom::multi::many1;
     use nom::sequence::terminated;
 
+    use advent_of_code_2023::decimal;
+    use advent_of_code_2023::span::{located, Span, spanned, Spanned};
+
     use crate::{Engine, Number, Position, Symbol};
 
-    pub(crate) fn parse(input: &str) -> IResult<&str, Engine> {
-        let parser = many1(terminated(parse_line, opt(line_ending)));
+    pub(crate) fn parse(input: Span) -> IResult<Span, Engine> {
+        let parser = many1(terminated(parse_line, opt(located(line_ending))));
         let (input, lines) = context("parse", parser)(input)?;
         let mut symbols = Vec::new();
         let mut numbers = Vec::new();
-        for (row_index, row) in lines.into_iter().enumerate() {
-            let row_index = row_index as u32;
-            let mut column = 0u32;
-            for t in row {
-                match t {
-                    Type::Period => column += 1,
-                    Type::Symbol(name) => {
-                        symbols.push(Symbol { name, position: Position::new(row_index, column) });
-                        column += 1;
-                    }
-                    Type::Number { value, size } => {
-                        numbers.push(Number::new(value, row_index, column, size as u32));
-                        column += size as u32;
-                    }
-                }
+        for token in lines.into_iter().flatten() {
+            let (row, column) = (token.y() as u32, token.x() as u32);
+            match *token.value() {
+                Type::Period => {}
+                Type::Symbol(name) => symbols.push(Symbol { name, position: Position::new(row, column) }),
+                Type::Number(value) => numbers.push(Number::new(value, row, column, token.len() as u32)),
             }
         }
         let engine = Engine { symbols, numbers };
@@ -156,26 +150,20 @@ mod parser {
     }
 
     fn parse_period(input: &str) -> IResult<&str, Type> {
-        map(tag("."), |_| Type::Period)(input)
+        value(Type::Period, tag("."))(input)
     }
 
     fn parse_symbol(input: &str) -> IResult<&str, Type> {
-        let verified = verify(take(1usize), |s: &str| {
-            let c: char = s.chars().next().unwrap();
-            !c.is_alphanumeric() && c != '.' && c != '\n'
-        });
-        map(verified, |s: &str| Type::Symbol(s.chars().next().unwrap()))(input)
+        let symbol = satisfy(|c| !c.is_alphanumeric() && c != '.' && c != '\n' && c != '\r');
+        map(symbol, Type::Symbol)(input)
     }
 
     fn parse_number(input: &str) -> IResult<&str, Type> {
-        map_res(digit1, |s: &str| {
-            s.parse::<u32>()
-                .map(|num| Type::Number { value: num, size: s.len() })
-        })(input)
+        map(decimal, Type::Number)(input)
     }
 
-    fn parse_line(input: &str) -> IResult<&str, Vec<Type>> {
-        many1(alt((parse_symbol, parse_number, parse_period)))(input)
+    fn parse_line(input: Span) -> IResult<Span, Vec<Spanned<Type>>> {
+        many1(spanned(located(alt((parse_symbol, parse_number, parse_period))))).parse(input)
     }
 
 
@@ -183,10 +171,7 @@ mod parser {
     enum Type {
         Period,
         Symbol(char),
-        Number {
-            value: u32,
-            size: usize,
-        },
+        Number(u32),
     }
 
 
@@ -202,6 +187,8 @@ mod parser {
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code_2023::span::Span;
+
     use crate::parser::parse;
     use crate::solve2;
 
@@ -218,7 +205,7 @@ mod tests {
 
     #[test]
     fn check_v2() {
-        let (_, engine) = parse(INPUT).unwrap();
+        let (_, engine) = parse(Span::new(INPUT)).unwrap();
         let result = solve2(&engine);
         assert_eq!(result, 467835);
     }
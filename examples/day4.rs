@@ -60,14 +60,14 @@ impl Card {
 }
 
 mod parser {
-    use nom::{IResult, Parser};
+    use nom::IResult;
     use nom::bytes::complete::tag;
-    use nom::character::complete::line_ending;
+    use nom::character::complete::{line_ending, space0};
     use nom::combinator::opt;
-    use nom::multi::{many0, many1};
-    use nom::sequence::{delimited, separated_pair, terminated};
+    use nom::multi::many1;
+    use nom::sequence::{pair, separated_pair, terminated};
 
-    use advent_of_code_2023::decimal;
+    use advent_of_code_2023::{labeled_id, numbers_list};
 
     use crate::Card;
 
@@ -76,16 +76,12 @@ mod parser {
     }
 
     fn parse_card(input: &str) -> IResult<&str, Card> {
-        let (input, card_id) = delimited(terminated(tag("Card"), many1(tag(" "))), decimal::<u32>, tag(":"))(input)?;
-        let (input, (winning, your)) = separated_pair(parse_numbers, tag("|"), parse_numbers)(input)?;
+        let (input, card_id) = labeled_id::<u32, _>("Card")(input)?;
+        let (input, (winning, your)) = separated_pair(numbers_list::<u32>, pair(space0, tag("|")), numbers_list::<u32>)(input)?;
         let card = Card::new(card_id, winning.into_iter().collect(), your.into_iter().collect());
         Ok((input, card))
     }
 
-    fn parse_numbers(input: &str) -> IResult<&str, Vec<u32>> {
-        many1(delimited(many0(tag(" ")), decimal::<u32>, many0(tag(" "))))(input)
-    }
-
     #[cfg(test)]
     mod tests {
         #[test]
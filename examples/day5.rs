@@ -43,47 +43,35 @@ fn solve2(input: &Input) -> Output {
 }
 
 mod parser {
+    use std::collections::HashMap;
+
     use nom::{IResult, Parser};
-    use nom::bytes::complete::tag;
+    use nom::bytes::complete::{tag, take_till1};
     use nom::character::complete::line_ending;
-    use nom::combinator::{map, opt, value};
-    use nom::multi::{many1, separated_list1};
-    use nom::sequence::{delimited, pair, terminated, tuple};
+    use nom::combinator::{map, opt};
+    use nom::error::{ErrorKind, make_error};
+    use nom::multi::many1;
+    use nom::sequence::{terminated, tuple};
     use tracing::debug;
-    use advent_of_code_2023::integer;
+    use advent_of_code_2023::{blank_lines, integer, labeled, numbers_list, sections};
 
     use crate::{Almanac, AlmanacMap, Input, Seeds};
 
     pub(crate) fn parse(input: &str) -> IResult<&str, Input> {
-        let parsers = (terminated(parse_seeds, empty_line),
-                       terminated(parse_map("seed-to-soil"), empty_line),
-                       terminated(parse_map("soil-to-fertilizer"), empty_line),
-                       terminated(parse_map("fertilizer-to-water"), empty_line),
-                       terminated(parse_map("water-to-light"), empty_line),
-                       terminated(parse_map("light-to-temperature"), empty_line),
-                       terminated(parse_map("temperature-to-humidity"), empty_line),
-                       terminated(parse_map("humidity-to-location"), empty_line),
-        );
-        let (input, results) = tuple(parsers).parse(input)?;
-        let (
-            seeds,
-            seed_to_soil,
-            soil_to_fertilizer,
-            fertilizer_to_water,
-            water_to_light,
-            light_to_temperature,
-            temperature_to_humidity,
-            humidity_to_location,
-        ) = results;
+        let (input, seeds) = terminated(parse_seeds, blank_lines)(input)?;
+        let (input, maps) = sections(map_name, parse_map)(input)?;
+        let mut maps = maps.into_iter().collect::<HashMap<_, _>>();
+        let mut take = |name: &str| maps.remove(name)
+            .ok_or_else(|| nom::Err::Failure(make_error(input, ErrorKind::Verify)));
 
         let almanac = Almanac {
-            seed_to_soil,
-            soil_to_fertilizer,
-            fertilizer_to_water,
-            water_to_light,
-            light_to_temperature,
-            temperature_to_humidity,
-            humidity_to_location,
+            seed_to_soil: take("seed-to-soil")?,
+            soil_to_fertilizer: take("soil-to-fertilizer")?,
+            fertilizer_to_water: take("fertilizer-to-water")?,
+            water_to_light: take("water-to-light")?,
+            light_to_temperature: take("light-to-temperature")?,
+            temperature_to_humidity: take("temperature-to-humidity")?,
+            humidity_to_location: take("humidity-to-location")?,
         };
 
         debug!("Parsed almanac");
@@ -92,40 +80,31 @@ mod parser {
 
     fn parse_seeds(input: &str) -> IResult<&str, Seeds> {
         debug!("Parsing seeds");
-        delimited(
-            tag("seeds: "),
-            separated_list1(tag(" "), integer),
-            opt(line_ending),
-        )(input)
+        terminated(labeled("seeds", numbers_list), opt(line_ending))(input)
     }
 
-    fn empty_line(input: &str) -> IResult<&str, ()> {
-        value((), opt(line_ending))(input)
+    fn map_name(input: &str) -> IResult<&str, &str> {
+        terminated(take_till1(|c| c == ' '), tag(" map:"))(input)
     }
 
-    fn parse_map(name: &str) -> impl for<'parser> FnMut(&'parser str) -> IResult<&'parser str, AlmanacMap> + '_ {
-        move |input| {
-            let (input, _) = terminated(pair(tag(name), tag(" map:")), line_ending)(input)?;
-
-            let entry = map(
-                terminated(
-                    tuple((integer, tag(" "), integer, tag(" "), integer)),
-                    opt(line_ending),
-                ), |(destination, _, source, _, length)| Entry {
-                    source,
-                    destination,
-                    length,
-                });
-            let (input, entries) = many1(entry)(input)?;
-
-            let mut map = AlmanacMap::default();
-
-            for Entry { source, destination, length } in entries {
-                map.add(source, destination, length);
-            }
-            debug!("Parsed {} map", name);
-            Ok((input, map))
+    fn parse_map(input: &str) -> IResult<&str, AlmanacMap> {
+        let entry = map(
+            terminated(
+                tuple((integer, tag(" "), integer, tag(" "), integer)),
+                opt(line_ending),
+            ), |(destination, _, source, _, length)| Entry {
+                source,
+                destination,
+                length,
+            });
+        let (input, entries) = many1(entry).parse(input)?;
+
+        let mut map = AlmanacMap::default();
+
+        for Entry { source, destination, length } in entries {
+            map.add(source, destination, length);
         }
+        Ok((input, map))
     }
 
     struct Entry {
@@ -38,28 +38,21 @@ fn solve2(input: &Input2) -> Output {
 mod parser {
     use std::str::FromStr;
 
-    use nom::{IResult, Parser};
-    use nom::bytes::complete::tag;
-    use nom::character::complete::{digit1, line_ending};
-    use nom::combinator::{map_res, opt};
-    use nom::multi::{many1, separated_list1};
-    use nom::sequence::{delimited, pair};
+    use nom::IResult;
+    use nom::character::complete::{digit1, space1};
+    use nom::error::{ErrorKind, make_error};
+    use nom::multi::separated_list1;
 
-    use advent_of_code_2023::integer;
+    use advent_of_code_2023::{labeled_map, numbers_list};
 
     use crate::{Distance, Input, Input2, Race, Time};
 
     pub(crate) fn parse(input: &str) -> IResult<&str, Input> {
-        let (input, time) = delimited(
-            pair(tag("Time:"), many1(tag(" "))),
-            separated_list1(many1(tag(" ")), integer::<Time>),
-            opt(line_ending),
-        )(input)?;
-        let (input, distances) = delimited(
-            pair(tag("Distance:"), many1(tag(" "))),
-            separated_list1(many1(tag(" ")), integer::<Distance>),
-            opt(line_ending),
-        )(input)?;
+        let (input, mut fields) = labeled_map(numbers_list::<u64>)(input)?;
+        let mut field = |name| fields.remove(name)
+            .ok_or_else(|| nom::Err::Failure(make_error(input, ErrorKind::Verify)));
+        let time: Vec<Time> = field("Time")?;
+        let distances: Vec<Distance> = field("Distance")?;
 
         let races = time.into_iter().zip(distances.into_iter())
             .map(|(time, distance)| Race::new(time, distance))
@@ -69,18 +62,12 @@ mod parser {
     }
 
     pub(crate) fn parse2(input: &str) -> IResult<&str, Input2> {
-        let (input, time) = map_res(
-            delimited(
-                pair(tag("Time:"), many1(tag(" "))),
-                separated_list1(many1(tag(" ")), digit1),
-                opt(line_ending),
-            ), |vec| make_number::<Time>(&vec))(input)?;
-        let (input, distance) = map_res(
-            delimited(
-                pair(tag("Distance:"), many1(tag(" "))),
-                separated_list1(many1(tag(" ")), digit1),
-                opt(line_ending),
-            ), |vec| make_number::<Distance>(&vec))(input)?;
+        let (input, fields) = labeled_map(separated_list1(space1, digit1))(input)?;
+        let field = |name| fields.get(name)
+            .and_then(|digits| make_number(digits).ok())
+            .ok_or_else(|| nom::Err::Failure(make_error(input, ErrorKind::Verify)));
+        let time: Time = field("Time")?;
+        let distance: Distance = field("Distance")?;
 
         let race = Race::new(time, distance);
 
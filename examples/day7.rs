@@ -158,6 +158,20 @@ impl Hand {
     }
 }
 
+impl FromStr for Hand {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cards = s.chars()
+            .map(Card::try_from)
+            .collect::<Result<Vec<Card>, _>>()?;
+        if cards.len() != 5 {
+            return Err(ParseCardError);
+        }
+        Ok(Hand::new(&cards))
+    }
+}
+
 type Bid = u32;
 
 fn main() {
@@ -177,15 +191,14 @@ mod parser {
     use std::str::FromStr;
 
     use nom::{IResult, Parser};
-    use nom::bytes::complete::tag;
-    use nom::character::complete::{line_ending, one_of};
-    use nom::combinator::{map_res, opt};
-    use nom::multi::{count, many1};
-    use nom::sequence::{separated_pair, terminated};
+    use nom::character::complete::line_ending;
+    use nom::combinator::opt;
+    use nom::multi::many1;
+    use nom::sequence::terminated;
 
-    use advent_of_code_2023::integer;
+    use advent_of_code_2023::scanf::scan;
 
-    use crate::{Bid, Card, Hand, Input, Input2, ParseCardError};
+    use crate::{Bid, Hand, Input, Input2};
 
     pub(crate) fn parse(input: &str) -> IResult<&str, Input> {
         many1(parse_line)(input)
@@ -196,17 +209,7 @@ mod parser {
     }
 
     fn parse_line(input: &str) -> IResult<&str, (Hand, Bid)> {
-        terminated(separated_pair(parse_hand, tag(" "), integer::<Bid>), opt(line_ending))(input)
-    }
-
-    fn parse_hand(input: &str) -> IResult<&str, Hand> {
-        map_res(
-            count(one_of("23456789TJQKA"), 5),
-            |s| {
-                let cards = s.into_iter().map(|c: char| Card::try_from(c))
-                    .collect::<Result<Vec<Card>, _>>()?;
-                Ok::<Hand, ParseCardError>(Hand::new(&cards))
-            })(input)
+        terminated(scan!("{} {}", Hand, Bid), opt(line_ending))(input)
     }
 
     #[cfg(test)]
@@ -80,20 +80,23 @@ mod parser {
     use nom::{IResult, Parser};
     use nom::branch::alt;
     use nom::bytes::complete::tag;
-    use nom::character::complete::{alphanumeric1, line_ending};
+    use nom::character::complete::line_ending;
     use nom::combinator::{map, opt, value};
     use nom::multi::many1;
-    use nom::sequence::{delimited, separated_pair, terminated};
+    use nom::sequence::terminated;
+
+    use advent_of_code_2023::blank_lines;
+    use advent_of_code_2023::scanf::scan;
 
     use crate::{Input, Input2, Location, Maps, Move};
 
     pub(crate) fn parse(input: &str) -> IResult<&str, Input> {
         let (input, moves) = moves(input)?;
-        let (input, _) = empty_line(input)?;
+        let (input, _) = blank_lines(input)?;
         let (input, maps) = map(many1(map_entry), |entries| {
             let mut maps = Maps::default();
-            for (key, (left, right)) in entries {
-                maps.put(Location::new(key.to_string()), (Location::new(left.to_string()), Location::new(right.to_string())));
+            for (key, left, right) in entries {
+                maps.put(Location::new(key), (Location::new(left), Location::new(right)));
             }
             maps
         })(input)?;
@@ -111,15 +114,11 @@ mod parser {
         terminated(many1(alt((left, right))), line_ending)(input)
     }
 
-    fn map_entry(input: &str) -> IResult<&str, (&str, (&str, &str))> {
-        terminated(separated_pair(alphanumeric1, tag(" = "), delimited(tag("("), separated_pair(alphanumeric1, tag(", "), alphanumeric1), tag(")"))), opt(line_ending))
+    fn map_entry(input: &str) -> IResult<&str, (String, String, String)> {
+        terminated(scan!("{} = ({}, {})", String, String, String), opt(line_ending))
             .parse(input)
     }
 
-    fn empty_line(input: &str) -> IResult<&str, ()> {
-        value((), opt(line_ending))(input)
-    }
-
     #[cfg(test)]
     mod tests {
         #[test]
@@ -2,35 +2,29 @@ use std::str::FromStr;
 
 use rayon::prelude::*;
 
-use advent_of_code_2023::{execute, Type};
+use advent_of_code_2023::{execute_lines, Type};
 
-type Input = Vec<Vec<i64>>;
-type Input2 = Input;
 type Output = i64;
 
 const DAY: u8 = 9;
 
 fn main() {
-    execute(DAY, Type::Demo, parser::parse, |values| solve1(values));
-    execute(DAY, Type::Task1, parser::parse, |values| solve1(values));
-    execute(DAY, Type::Demo, parser::parse2, |values| solve2(values));
-    execute(DAY, Type::Task2, parser::parse2, |values| solve2(values));
+    execute_lines(DAY, Type::Demo, parser::parse_line, |lines| solve1(lines));
+    execute_lines(DAY, Type::Task1, parser::parse_line, |lines| solve1(lines));
+    execute_lines(DAY, Type::Demo, parser::parse_line, |lines| solve2(lines));
+    execute_lines(DAY, Type::Task2, parser::parse_line, |lines| solve2(lines));
 }
 
-fn solve1(input: &Input) -> Output {
-    let groups = input.iter()
-        .map(|seq| SequenceGroup::new(seq.clone()))
-        .collect::<Vec<_>>();
-
-    groups.iter().map(|group| group.extrapolate_next()).sum()
+fn solve1(lines: impl Iterator<Item=Vec<i64>> + Send) -> Output {
+    lines.par_bridge()
+        .map(|seq| SequenceGroup::new(seq).extrapolate_next())
+        .sum()
 }
 
-fn solve2(input: &Input2) -> Output {
-    let groups = input.iter()
-        .map(|seq| SequenceGroup::new(seq.clone()))
-        .collect::<Vec<_>>();
-
-    groups.iter().map(|group| group.extrapolate_prev()).sum()
+fn solve2(lines: impl Iterator<Item=Vec<i64>> + Send) -> Output {
+    lines.par_bridge()
+        .map(|seq| SequenceGroup::new(seq).extrapolate_prev())
+        .sum()
 }
 
 
@@ -76,32 +70,11 @@ impl SequenceGroup {
 mod parser {
     use std::str::FromStr;
 
-    use nom::{IResult, Parser};
-    use nom::bytes::complete::tag;
-    use nom::character::complete::{digit1, line_ending};
-    use nom::combinator::opt;
-    use nom::multi::{many1, separated_list1};
-    use nom::sequence::{pair, terminated};
-    use advent_of_code_2023::integer;
-
-    use crate::{Input, Input2};
-
-    pub(crate) fn parse(input: &str) -> IResult<&str, Input> {
-        many1(parse_line)(input)
-    }
-
-    fn parse_line(input: &str) -> IResult<&str, Vec<i64>> {
-        terminated(
-            separated_list1(
-                tag(" "),
-                integer::<i64>,
-            ),
-            opt(line_ending),
-        )(input)
-    }
+    use nom::IResult;
+    use advent_of_code_2023::numbers_list;
 
-    pub(crate) fn parse2(input: &str) -> IResult<&str, Input2> {
-        parse(input)
+    pub(crate) fn parse_line(input: &str) -> IResult<&str, Vec<i64>> {
+        numbers_list::<i64>(input)
     }
 
     #[cfg(test)]
@@ -116,30 +89,29 @@ mod parser {
 
 #[cfg(test)]
 mod tests {
-    use crate::parser::parse;
+    use std::io::Cursor;
+
+    use advent_of_code_2023::stream::ParsedLines;
+
+    use crate::parser::parse_line;
     use crate::{solve1, solve2};
 
-    #[test]
-    fn check() {
-        let input = "0 3 6 9 12 15
+    const INPUT: &str = "0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45";
-        let (remains, sequences) = parse(input).unwrap();
-        assert_eq!(remains, "");
-        let result = solve1(&sequences);
+
+    #[test]
+    fn check() {
+        let mut lines = ParsedLines::new(Cursor::new(INPUT), parse_line);
+        let result = solve1(&mut lines);
+        assert_eq!(lines.error(), None);
         assert_eq!(result, 114);
     }
     #[test]
     fn check_2() {
-        let input = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
-        let (remains, sequences) = parse(input).unwrap();
-        assert_eq!(remains, "");
-        let result = solve2(&sequences);
+        let mut lines = ParsedLines::new(Cursor::new(INPUT), parse_line);
+        let result = solve2(&mut lines);
+        assert_eq!(lines.error(), None);
         assert_eq!(result, 2);
     }
 }
-
-
-
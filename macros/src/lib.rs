@@ -0,0 +1,201 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Expr, ExprLit, Fields, Ident, Lit, LitChar, LitStr, Meta, parse_macro_input, Token, Type};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+
+/// Derives `cell::FromChar`, `TryFrom<char>`, an inherent nom `parse` and an `ALL` constant
+/// for an enum of unit variants marked with `#[ch = '.']`.
+#[proc_macro_derive(FromChar, attributes(ch))]
+pub fn derive_from_char(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let variants = match variants(&input) {
+        Ok(variants) => variants,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    for (i, (_, ch)) in variants.iter().enumerate() {
+        if let Some((_, other)) = variants[..i].iter().find(|(_, other)| other.value() == ch.value()) {
+            let mut err = Error::new(ch.span(), format!("Duplicate cell char {:?}", ch.value()));
+            err.combine(Error::new(other.span(), "first used here"));
+            return err.to_compile_error().into();
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let count = variants.len();
+    let idents = variants.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
+    let chars = variants.iter().map(|(_, ch)| ch).collect::<Vec<_>>();
+
+    quote! {
+        impl #impl_generics ::advent_of_code_2023::cell::FromChar for #name #type_generics #where_clause {
+            fn from_char(c: char) -> ::std::option::Option<Self> {
+                match c {
+                    #(#chars => ::std::option::Option::Some(Self::#idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl #impl_generics ::std::convert::TryFrom<char> for #name #type_generics #where_clause {
+            type Error = char;
+
+            fn try_from(c: char) -> ::std::result::Result<Self, char> {
+                <Self as ::advent_of_code_2023::cell::FromChar>::from_char(c).ok_or(c)
+            }
+        }
+
+        #[allow(dead_code)]
+        impl #impl_generics #name #type_generics #where_clause {
+            pub const ALL: [Self; #count] = [#(Self::#idents),*];
+
+            pub fn parse(input: &str) -> ::nom::IResult<&str, Self> {
+                <Self as ::advent_of_code_2023::cell::FromChar>::parse(input)
+            }
+        }
+    }.into()
+}
+
+/// Derives `cell::ToChar` and `Display` for an enum of unit variants marked with `#[ch = '.']`.
+#[proc_macro_derive(ToChar, attributes(ch))]
+pub fn derive_to_char(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let variants = match variants(&input) {
+        Ok(variants) => variants,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let idents = variants.iter().map(|(ident, _)| ident);
+    let chars = variants.iter().map(|(_, ch)| ch);
+
+    quote! {
+        impl #impl_generics ::advent_of_code_2023::cell::ToChar for #name #type_generics #where_clause {
+            fn to_char(&self) -> char {
+                match self {
+                    #(Self::#idents => #chars,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #type_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::fmt::Write::write_char(f, ::advent_of_code_2023::cell::ToChar::to_char(self))
+            }
+        }
+    }.into()
+}
+
+fn variants(input: &DeriveInput) -> Result<Vec<(Ident, LitChar)>, Error> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new(Span::call_site(), "Only enums of unit variants are supported"));
+    };
+    data.variants.iter()
+        .map(|variant| {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(Error::new_spanned(variant, "Only unit variants are supported"));
+            }
+            let ch = variant.attrs.iter()
+                .find(|attr| attr.path().is_ident("ch"))
+                .ok_or_else(|| Error::new_spanned(variant, "Missing #[ch = '.'] attribute"))?;
+            match &ch.meta {
+                Meta::NameValue(meta) => match &meta.value {
+                    Expr::Lit(ExprLit { lit: Lit::Char(ch), .. }) => Ok((variant.ident.clone(), ch.clone())),
+                    value => Err(Error::new_spanned(value, "Expected a char literal")),
+                },
+                meta => Err(Error::new_spanned(meta, "Expected #[ch = '.']")),
+            }
+        })
+        .collect()
+}
+
+/// Compiles a `scanf`-like template into a nom parser of one line.
+///
+/// `scan!("{} = ({}, {})", String, String, String)` parses `AAA = (BBB, CCC)` into
+/// `("AAA", "BBB", "CCC")`. Every `{}` is parsed with `FromStr` of its type,
+/// `{{` and `}}` match literal braces.
+#[proc_macro]
+pub fn scan(input: TokenStream) -> TokenStream {
+    let ScanInput { template, types } = parse_macro_input!(input as ScanInput);
+    let pieces = match split_template(&template) {
+        Ok(pieces) => pieces,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    if pieces.len() != types.len() + 1 {
+        let message = format!("Template has {} captures, but {} types given", pieces.len() - 1, types.len());
+        return Error::new(template.span(), message).to_compile_error().into();
+    }
+    if pieces[1..].iter().rev().skip(1).any(|piece| piece.is_empty()) {
+        return Error::new(template.span(), "Captures must be separated by a literal").to_compile_error().into();
+    }
+
+    let head = &pieces[0];
+    let steps = types.iter().zip(&pieces[1..]).enumerate().map(|(i, (ty, literal))| {
+        let value = Ident::new(&format!("value_{i}"), Span::call_site());
+        let next = if literal.is_empty() {
+            quote!(::std::option::Option::None)
+        } else {
+            quote!(::std::option::Option::Some(#literal))
+        };
+        quote! {
+            let (input, #value) = ::advent_of_code_2023::scanf::capture::<#ty>(input, #next)?;
+            let (input, _) = ::advent_of_code_2023::scanf::literal(input, #literal)?;
+        }
+    });
+    let values = (0..types.len()).map(|i| Ident::new(&format!("value_{i}"), Span::call_site()));
+    let types = types.iter();
+
+    quote! {
+        {
+            fn scan(input: &str) -> ::nom::IResult<&str, (#(#types,)*)> {
+                let (input, _) = ::advent_of_code_2023::scanf::literal(input, #head)?;
+                #(#steps)*
+                ::std::result::Result::Ok((input, (#(#values,)*)))
+            }
+            scan
+        }
+    }.into()
+}
+
+struct ScanInput {
+    template: LitStr,
+    types: Punctuated<Type, Token![,]>,
+}
+
+impl Parse for ScanInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let template = input.parse()?;
+        let types = if input.is_empty() {
+            Punctuated::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            Punctuated::parse_terminated(input)?
+        };
+        Ok(Self { template, types })
+    }
+}
+
+/// Literal pieces of a template around its `{}` captures.
+fn split_template(template: &LitStr) -> Result<Vec<String>, Error> {
+    let mut pieces = vec![String::new()];
+    let mut chars = template.value().chars().collect::<Vec<_>>().into_iter().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                pieces.last_mut().unwrap().push(c);
+            }
+            ('{', Some('}')) => {
+                chars.next();
+                pieces.push(String::new());
+            }
+            ('{', _) | ('}', _) => {
+                return Err(Error::new(template.span(), "Unmatched brace, use `{}` for captures and `{{`/`}}` for braces"));
+            }
+            _ => pieces.last_mut().unwrap().push(c),
+        }
+    }
+    Ok(pieces)
+}
@@ -0,0 +1,276 @@
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use nom::error::{ErrorKind, make_error};
+use nom::IResult;
+
+/// Multi-literal matcher built once from a `(literal, value)` table.
+///
+/// Finds all overlapping occurrences of every literal in one linear pass over the haystack,
+/// e.g. digit words `"one"`..`"nine"` in `"eightwone"`.
+#[derive(Debug, Clone)]
+pub struct AhoCorasick<V> {
+    nodes: Vec<Node>,
+    patterns: Vec<(usize, V)>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    next: Vec<(u8, usize)>,
+    fail: usize,
+    /// Patterns ending at this node, longest first, including the ones of the failure chain.
+    outputs: Vec<usize>,
+}
+
+impl Node {
+    #[inline(always)]
+    fn goto(&self, byte: u8) -> Option<usize> {
+        self.next.binary_search_by_key(&byte, |(b, _)| *b)
+            .ok()
+            .map(|i| self.next[i].1)
+    }
+}
+
+const ROOT: usize = 0;
+
+impl<V> AhoCorasick<V> {
+    pub fn new<S: AsRef<str>>(table: impl IntoIterator<Item=(S, V)>) -> Self {
+        let mut nodes = vec![Node::default()];
+        let mut patterns = Vec::new();
+        for (literal, value) in table {
+            let literal = literal.as_ref();
+            assert!(!literal.is_empty(), "Empty literal");
+            let mut state = ROOT;
+            for &byte in literal.as_bytes() {
+                state = match nodes[state].goto(byte) {
+                    Some(next) => next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        let transitions = &mut nodes[state].next;
+                        let at = transitions.partition_point(|(b, _)| *b < byte);
+                        transitions.insert(at, (byte, next));
+                        next
+                    }
+                };
+            }
+            nodes[state].outputs.push(patterns.len());
+            patterns.push((literal.len(), value));
+        }
+
+        let mut queue = VecDeque::from_iter(nodes[ROOT].next.iter().map(|(_, n)| *n));
+        while let Some(state) = queue.pop_front() {
+            for (byte, next) in nodes[state].next.clone() {
+                let mut fail = nodes[state].fail;
+                let fail = loop {
+                    if let Some(target) = nodes[fail].goto(byte) {
+                        break target;
+                    }
+                    if fail == ROOT {
+                        break ROOT;
+                    }
+                    fail = nodes[fail].fail;
+                };
+                nodes[next].fail = fail;
+                let inherited = nodes[fail].outputs.clone();
+                nodes[next].outputs.extend(inherited);
+                queue.push_back(next);
+            }
+        }
+        Self { nodes, patterns }
+    }
+
+    /// All matches ordered by end position, longer matches first on the same end.
+    pub fn find_iter<'a, 'h>(&'a self, haystack: &'h str) -> FindIter<'a, 'h, V> {
+        FindIter { matcher: self, haystack: haystack.as_bytes(), position: 0, state: ROOT, output: 0 }
+    }
+
+    /// Leftmost match, the shortest one if several start at the same position.
+    pub fn first<'a>(&'a self, haystack: &str) -> Option<Match<'a, V>> {
+        let max_len = self.max_len();
+        let mut best: Option<Match<'a, V>> = None;
+        for found in self.find_iter(haystack) {
+            if let Some(best) = best {
+                if found.end() >= best.start() + max_len {
+                    break;
+                }
+            }
+            if best.is_none_or(|best| found.start() < best.start()) {
+                best = Some(found);
+            }
+        }
+        best
+    }
+
+    /// Rightmost match, the longest one if several start at the same position.
+    pub fn last<'a>(&'a self, haystack: &str) -> Option<Match<'a, V>> {
+        self.find_iter(haystack)
+            .fold(None, |best: Option<Match<'a, V>>, found| match best {
+                Some(best) if best.start() > found.start() => Some(best),
+                Some(best) if best.start() == found.start() && best.len() >= found.len() => Some(best),
+                _ => Some(found),
+            })
+    }
+
+    /// nom parser matching the longest literal at the start of the input.
+    pub fn parser(&self) -> impl Fn(&str) -> IResult<&str, V> + '_ where V: Clone {
+        move |input: &str| {
+            let mut state = ROOT;
+            let mut longest = None;
+            for (depth, &byte) in input.as_bytes().iter().enumerate() {
+                match self.nodes[state].goto(byte) {
+                    Some(next) => state = next,
+                    None => break,
+                }
+                if let Some(&pattern) = self.nodes[state].outputs.first() {
+                    let (len, value) = &self.patterns[pattern];
+                    if *len == depth + 1 {
+                        longest = Some((len, value));
+                    }
+                }
+            }
+            match longest {
+                Some((len, value)) => Ok((&input[*len..], value.clone())),
+                None => Err(nom::Err::Error(make_error(input, ErrorKind::Tag))),
+            }
+        }
+    }
+
+    fn max_len(&self) -> usize {
+        self.patterns.iter().map(|(len, _)| *len).max().unwrap_or(0)
+    }
+
+    #[inline(always)]
+    fn step(&self, mut state: usize, byte: u8) -> usize {
+        loop {
+            if let Some(next) = self.nodes[state].goto(byte) {
+                return next;
+            }
+            if state == ROOT {
+                return ROOT;
+            }
+            state = self.nodes[state].fail;
+        }
+    }
+}
+
+pub struct FindIter<'a, 'h, V> {
+    matcher: &'a AhoCorasick<V>,
+    haystack: &'h [u8],
+    position: usize,
+    state: usize,
+    output: usize,
+}
+
+impl<'a, V> Iterator for FindIter<'a, '_, V> {
+    type Item = Match<'a, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let matcher = self.matcher;
+        loop {
+            if let Some(&pattern) = matcher.nodes[self.state].outputs.get(self.output) {
+                self.output += 1;
+                let (len, value) = &matcher.patterns[pattern];
+                return Some(Match::new(self.position - len, self.position, value));
+            }
+            let &byte = self.haystack.get(self.position)?;
+            self.state = matcher.step(self.state, byte);
+            self.position += 1;
+            self.output = 0;
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Match<'a, V> {
+    start: usize,
+    end: usize,
+    value: &'a V,
+}
+
+impl<V> Clone for Match<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for Match<'_, V> {}
+
+impl<'a, V> Match<'a, V> {
+    fn new(start: usize, end: usize, value: &'a V) -> Self {
+        Self { start, end, value }
+    }
+
+    /// Byte offset of the match start.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Byte offset right after the match.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    pub fn value(&self) -> &'a V {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rstest::rstest;
+
+    use super::AhoCorasick;
+
+    fn matcher() -> AhoCorasick<u32> {
+        AhoCorasick::new([("one", 1), ("two", 2), ("eight", 8), ("nine", 9), ("ne", 0), ("t", 10)])
+    }
+
+    #[rstest]
+    #[case("twone", vec![(0, 10), (0, 2), (2, 1), (3, 0)])]
+    #[case("xeightwo", vec![(1, 8), (5, 10), (5, 2)])]
+    #[case("ninine", vec![(2, 9), (4, 0)])]
+    #[case("ñone", vec![(2, 1), (3, 0)])]
+    #[case("", vec![])]
+    fn check_find_iter(#[case] input: &str, #[case] expected: Vec<(usize, u32)>) {
+        let matcher = matcher();
+        let found = matcher.find_iter(input)
+            .map(|m| (m.start(), *m.value()))
+            .collect_vec();
+        assert_eq!(expected, found);
+    }
+
+    #[test]
+    fn check_first_last() {
+        let matcher = matcher();
+        let first = matcher.first("xtwone").expect("has match");
+        assert_eq!((1, 10), (first.start(), *first.value()));
+        let last = matcher.last("xeightwo").expect("has match");
+        assert_eq!((5, 2), (last.start(), *last.value()));
+        assert!(matcher.first("xyz").is_none());
+        assert!(matcher.last("xyz").is_none());
+    }
+
+    #[test]
+    fn check_parser() {
+        let matcher = matcher();
+        let parser = matcher.parser();
+        assert_eq!(Ok(("ight", 1)), parser("oneight"));
+        assert_eq!(Ok(("", 2)), parser("two"));
+        assert_eq!(Ok(("hree", 10)), parser("three"));
+        assert!(parser("xone").is_err());
+    }
+}
@@ -0,0 +1,68 @@
+use nom::error::{ErrorKind, make_error};
+use nom::IResult;
+
+pub use advent_of_code_2023_macros::{FromChar, ToChar};
+
+/// Grid cell read from a single input char.
+///
+/// Usually derived: `#[derive(FromChar)]` with `#[ch = '#']` on every variant.
+pub trait FromChar: Sized {
+    fn from_char(c: char) -> Option<Self>;
+
+    fn parse(input: &str) -> IResult<&str, Self> {
+        let mut chars = input.chars();
+        match chars.next().and_then(Self::from_char) {
+            Some(cell) => Ok((chars.as_str(), cell)),
+            None => Err(nom::Err::Error(make_error(input, ErrorKind::Char))),
+        }
+    }
+}
+
+/// Grid cell printed as a single char, the inverse of [`FromChar`].
+pub trait ToChar {
+    fn to_char(&self) -> char;
+}
+
+impl FromChar for char {
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+impl ToChar for char {
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::multi::many1;
+
+    use crate::cell::{FromChar, ToChar};
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, FromChar, ToChar)]
+    enum Tile {
+        #[ch = '.']
+        Ash,
+        #[ch = '#']
+        Rock,
+    }
+
+    #[test]
+    fn check_from_char() {
+        assert_eq!(Ok(Tile::Rock), Tile::try_from('#'));
+        assert_eq!(Err('x'), Tile::try_from('x'));
+        assert_eq!([Tile::Ash, Tile::Rock], Tile::ALL);
+        let (remain, tiles) = many1(Tile::parse)("#.#\n").expect("tiles parsed");
+        assert_eq!("\n", remain);
+        assert_eq!(vec![Tile::Rock, Tile::Ash, Tile::Rock], tiles);
+    }
+
+    #[test]
+    fn check_to_char() {
+        assert_eq!('#', Tile::Rock.to_char());
+        let line = Tile::ALL.iter().map(|tile| tile.to_string()).collect::<String>();
+        assert_eq!(".#", line);
+    }
+}
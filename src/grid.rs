@@ -0,0 +1,271 @@
+use std::collections::{HashMap, VecDeque};
+use std::collections::hash_map;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+use crate::matrix::{Direction, Index, Matrix, Offset, Rect, Size};
+
+/// Read access shared by dense, sparse and wrapping grids,
+/// so searches like [`distances`] are written once.
+pub trait Grid {
+    type Cell;
+    type Index: Copy + Eq + Hash;
+    type Bounds;
+
+    /// Cell at `index`, `None` outside of the grid or for a missing sparse cell.
+    fn get(&self, index: Self::Index) -> Option<&Self::Cell>;
+
+    /// Side neighbors of `index` which belong to the grid.
+    fn neighbors(&self, index: Self::Index) -> impl Iterator<Item=Self::Index>;
+
+    fn bounds(&self) -> Self::Bounds;
+}
+
+/// Grid which cells can be replaced.
+pub trait GridMut: Grid {
+    fn set(&mut self, index: Self::Index, value: Self::Cell);
+}
+
+impl<T> Grid for Matrix<T> {
+    type Cell = T;
+    type Index = Index;
+    type Bounds = Rect;
+
+    fn get(&self, index: Index) -> Option<&T> {
+        self.try_get(index)
+    }
+
+    fn neighbors(&self, index: Index) -> impl Iterator<Item=Index> {
+        self.neighbors4(index)
+    }
+
+    fn bounds(&self) -> Rect {
+        Matrix::bounds(self)
+    }
+}
+
+impl<T> GridMut for Matrix<T> {
+    /// Panics outside of the matrix.
+    fn set(&mut self, index: Index, value: T) {
+        Matrix::set(self, index, value)
+    }
+}
+
+/// Signed grid coordinate without bounds.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+pub struct Point(i64, i64);
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self(x, y)
+    }
+
+    #[inline(always)]
+    pub fn x(&self) -> i64 {
+        self.0
+    }
+
+    #[inline(always)]
+    pub fn y(&self) -> i64 {
+        self.1
+    }
+
+    /// Takes a [`Direction`], a [`Direction8`](crate::matrix::Direction8) or any [`Offset`].
+    pub fn step(&self, direction: impl Into<Offset>) -> Point {
+        let offset = direction.into();
+        Point(self.0 + offset.dx() as i64, self.1 + offset.dy() as i64)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self(x, y)
+    }
+}
+
+impl From<Index> for Point {
+    fn from(index: Index) -> Self {
+        Self(index.x() as i64, index.y() as i64)
+    }
+}
+
+/// Unbounded grid keeping only set cells, coordinates may grow in any direction or go negative.
+#[derive(Debug, Clone, Default)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self { cells: HashMap::new(), bounds: None }
+    }
+
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some((min, max)) => (
+                Point(min.0.min(point.0), min.1.min(point.1)),
+                Point(max.0.max(point.0), max.1.max(point.1)),
+            ),
+        });
+        self.cells.insert(point, value)
+    }
+
+    /// Removes the cell, bounds shrink when it was on their edge.
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let removed = self.cells.remove(&point)?;
+        if let Some((min, max)) = self.bounds {
+            if point.0 == min.0 || point.1 == min.1 || point.0 == max.0 || point.1 == max.1 {
+                self.bounds = self.cells.keys().fold(None, |bounds, &point| match bounds {
+                    None => Some((point, point)),
+                    Some((min, max)) => Some((
+                        Point(min.0.min(point.0), min.1.min(point.1)),
+                        Point(max.0.max(point.0), max.1.max(point.1)),
+                    )),
+                });
+            }
+        }
+        Some(removed)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Set cells in no particular order.
+    pub fn iter(&self) -> hash_map::Iter<'_, Point, T> {
+        self.cells.iter()
+    }
+
+    /// Dense copy of the bounding box, missing cells are `fill`.
+    pub fn to_matrix(&self, fill: T) -> Matrix<T> where T: Clone {
+        let Some((min, max)) = self.bounds else {
+            return Matrix::filled(Size::new(0, 0), fill);
+        };
+        let size = Size::new((max.0 - min.0 + 1) as usize, (max.1 - min.1 + 1) as usize);
+        let mut matrix = Matrix::filled(size, fill);
+        for (point, value) in self.cells.iter() {
+            matrix.set_at((point.0 - min.0) as usize, (point.1 - min.1) as usize, value.clone());
+        }
+        matrix
+    }
+}
+
+impl<T> Grid for SparseGrid<T> {
+    type Cell = T;
+    type Index = Point;
+    /// Inclusive top left and bottom right corners, `None` when empty.
+    type Bounds = Option<(Point, Point)>;
+
+    fn get(&self, index: Point) -> Option<&T> {
+        self.cells.get(&index)
+    }
+
+    /// All 4 side neighbors, the grid is unbounded.
+    fn neighbors(&self, index: Point) -> impl Iterator<Item=Point> {
+        Direction::ALL.into_iter().map(move |direction| index.step(direction))
+    }
+
+    fn bounds(&self) -> Self::Bounds {
+        self.bounds
+    }
+}
+
+impl<T> GridMut for SparseGrid<T> {
+    fn set(&mut self, index: Point, value: T) {
+        self.insert(index, value);
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item=(Point, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (point, value) in iter {
+            grid.insert(point, value);
+        }
+        grid
+    }
+}
+
+/// Breadth first search from `start` over neighbors accepted by `can_enter`.
+///
+/// Returns the number of steps to every reached index. On a [`SparseGrid`] `can_enter`
+/// has to reject missing cells, and on a [`Wrapping`](crate::matrix::Wrapping) grid it has
+/// to limit how far the search goes, otherwise the search never ends.
+pub fn distances<G: Grid>(
+    grid: &G,
+    start: G::Index,
+    can_enter: impl Fn(G::Index, Option<&G::Cell>) -> bool,
+) -> HashMap<G::Index, usize> {
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(index) = queue.pop_front() {
+        let steps = distances[&index] + 1;
+        for next in grid.neighbors(index) {
+            if !distances.contains_key(&next) && can_enter(next, grid.get(next)) {
+                distances.insert(next, steps);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// Indexes connected to `start` through cells matching `same`, `start` included.
+///
+/// Never ends on a [`Wrapping`](crate::matrix::Wrapping) grid once the region crosses a tile edge,
+/// use [`distances`] with a bound there.
+pub fn flood_fill<G: Grid>(grid: &G, start: G::Index, same: impl Fn(&G::Cell) -> bool) -> Vec<G::Index> {
+    distances(grid, start, |_, cell| cell.is_some_and(&same))
+        .into_keys()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{distances, flood_fill, Grid, GridMut, Point, SparseGrid};
+    use crate::matrix::{Index, Matrix, Size};
+
+    #[test]
+    fn check_matrix_grid() {
+        let mut maze = Matrix::from(vec![vec!['S', '.', '#'], vec!['.', '#', '#'], vec!['.', '.', '.']]);
+        let steps = distances(&maze, Index::new(0, 0), |_, cell| cell != Some(&'#'));
+        assert_eq!(Some(&4), steps.get(&Index::new(2, 2)));
+        assert_eq!(None, steps.get(&Index::new(2, 0)));
+        assert_eq!(None, Grid::get(&maze, Index::new(3, 0)));
+        GridMut::set(&mut maze, Index::new(1, 0), '#');
+        assert_eq!(Size::new(3, 3), Grid::bounds(&maze).size());
+        assert_eq!(5, flood_fill(&maze, Index::new(0, 0), |cell| *cell != '#').len());
+    }
+
+    #[test]
+    fn check_sparse_grid() {
+        let mut grid = [(-2, 1), (-1, 1), (0, 1), (0, 0), (3, -4)]
+            .into_iter()
+            .map(|point| (Point::from(point), '#'))
+            .collect::<SparseGrid<_>>();
+        assert_eq!(Some((Point::new(-2, -4), Point::new(3, 1))), grid.bounds());
+        assert_eq!(4, flood_fill(&grid, Point::new(0, 0), |_| true).len());
+        assert_eq!(Some('#'), grid.remove(Point::new(3, -4)));
+        assert_eq!(Some((Point::new(-2, 0), Point::new(0, 1))), grid.bounds());
+        grid.set(Point::new(-2, 0), 'x');
+        let matrix = grid.to_matrix('.');
+        assert_eq!(Matrix::from(vec![vec!['x', '.', '#'], vec!['#', '#', '#']]), matrix);
+        assert_eq!(4, grid.neighbors(Point::new(100, -100)).count());
+    }
+}
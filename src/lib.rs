@@ -1,6 +1,16 @@
+extern crate self as advent_of_code_2023;
+
+pub mod aho_corasick;
+pub mod cell;
+pub mod grid;
 pub mod matrix;
+pub mod scanf;
+pub mod span;
+pub mod stream;
+pub mod testing;
 
 use std::{
+    collections::HashMap,
     fmt::Display,
     fs::File,
     io::{BufReader, Read},
@@ -14,13 +24,16 @@ use std::time::Instant;
 use colored::Colorize;
 use nom::{
     combinator::{map_res, recognize},
-    error::{make_error, ParseError}, IResult, Parser,
+    error::{FromExternalError, make_error, ParseError}, IResult, Parser,
 };
-use nom::bytes::complete::tag;
-use nom::character::complete::digit1;
-use nom::combinator::{consumed, opt};
-use nom::sequence::pair;
+use nom::bytes::complete::{tag, take_till1};
+use nom::character::complete::{digit1, line_ending, space0, space1};
+use nom::combinator::{consumed, map, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
 use num::{PrimInt, Unsigned};
+use span::Span;
+use stream::ParsedLines;
 use tracing::level_filters::LevelFilter;
 
 pub fn decimal<T>(input: &str) -> IResult<&str, T>
@@ -30,7 +43,14 @@ pub fn decimal<T>(input: &str) -> IResult<&str, T>
 }
 
 fn read_input(name: &str) -> String {
-    let file = File::open(name).unwrap_or_else(|_| {
+    let mut reader = BufReader::new(open_input(name));
+    let mut str = String::with_capacity(1024);
+    let _ = reader.read_to_string(&mut str);
+    str
+}
+
+fn open_input(name: &str) -> File {
+    File::open(name).unwrap_or_else(|_| {
         eprintln!("\n‼️ Error: There no file {file_name} ‼️ \n\n\
         Possible solutions 🫵: \n\
         \t⚡️ add the input file to {new_file} \n\
@@ -43,11 +63,7 @@ fn read_input(name: &str) -> String {
                   type = "Type".green(),
         );
         exit(-1);
-    });
-    let mut reader = BufReader::new(file);
-    let mut str = String::with_capacity(1024);
-    let _ = reader.read_to_string(&mut str);
-    str
+    })
 }
 
 pub enum Type {
@@ -78,6 +94,45 @@ pub fn integer<T: FromStr>(input: &str) -> IResult<&str, T> {
     map_res(consumed(pair(opt(tag("-")), digit1)), |(out, ..)| T::from_str(out))(input)
 }
 
+/// Parses whitespace separated integers, leading whitespace is skipped.
+///
+/// `" 7  15   30"` -> `[7, 15, 30]`
+pub fn numbers_list<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    preceded(space0, separated_list1(space1, integer))(input)
+}
+
+/// Parses `name:` followed by optional whitespace and `value`.
+///
+/// `labeled("Time", numbers_list)` parses `"Time:      7  15   30"`.
+pub fn labeled<'i, O, E: ParseError<&'i str>, F>(
+    name: &'i str,
+    value: F,
+) -> impl FnMut(&'i str) -> IResult<&'i str, O, E>
+    where F: Parser<&'i str, O, E> {
+    preceded(tuple((tag(name), tag(":"), space0)), value)
+}
+
+/// Parses `name N:` followed by optional whitespace and returns `N`.
+///
+/// `labeled_id("Card")` parses `"Card  12: "` into `12`.
+pub fn labeled_id<'i, T: FromStr, E>(name: &'i str) -> impl FnMut(&'i str) -> IResult<&'i str, T, E>
+    where E: ParseError<&'i str> + FromExternalError<&'i str, T::Err> {
+    delimited(pair(tag(name), space1), map_res(digit1, T::from_str), pair(tag(":"), space0))
+}
+
+/// Parses `name: value` lines into a map by name, for records with no fixed field order.
+///
+/// A name is everything before the first `:` of a line.
+pub fn labeled_map<'i, O, E: ParseError<&'i str>, F>(value: F) -> impl FnMut(&'i str) -> IResult<&'i str, HashMap<&'i str, O>, E>
+    where F: Parser<&'i str, O, E> {
+    let name = take_till1(|c| c == ':' || c == '\r' || c == '\n');
+    let field = separated_pair(name, pair(tag(":"), space0), value);
+    map(
+        terminated(separated_list1(line_ending, field), opt(line_ending)),
+        |fields| fields.into_iter().collect(),
+    )
+}
+
 pub fn execute<O, R>(
     day: u8,
     t: Type,
@@ -85,12 +140,71 @@ pub fn execute<O, R>(
     solve: impl Fn(&mut O) -> R,
 ) where
     R: Display,
+{
+    run(day, t, parse, solve)
+}
+
+/// [`execute`] for parsers of located input, see [`span`].
+pub fn execute_located<O, R>(
+    day: u8,
+    t: Type,
+    parse: impl for<'s> Fn(Span<'s>) -> IResult<Span<'s>, O>,
+    solve: impl Fn(&mut O) -> R,
+) where
+    R: Display,
+{
+    run(
+        day,
+        t,
+        |data| parse(Span::new(data))
+            .map(|(remain, parsed)| (*remain.fragment(), parsed))
+            .map_err(|err| err.map(|err| nom::error::Error::new(*err.input.fragment(), err.code))),
+        solve,
+    )
+}
+
+/// [`execute`] for line oriented inputs too big to be read at once.
+///
+/// `parse` runs on every line without its line ending and `solve` consumes the parsed lines
+/// while they are read, e.g. with `par_bridge()`. Lines stop at the first read or parse error,
+/// which is printed instead of the answer `solve` computed from the lines before it.
+pub fn execute_lines<O, R, P, S>(day: u8, t: Type, parse: P, solve: S)
+    where
+        R: Display,
+        P: for<'s> Fn(&'s str) -> IResult<&'s str, O>,
+        S: FnOnce(&mut ParsedLines<BufReader<File>, P>) -> R,
+{
+    let time = Instant::now();
+    let file_name = format!("examples/day{}/{}", day, t.file());
+    let mut lines = ParsedLines::new(BufReader::new(open_input(&file_name)), parse);
+    let result = match lines.solve(solve) {
+        Ok(result) => result,
+        Err(err) => {
+            eprintln!("{err}");
+            return;
+        }
+    };
+    println!("Day {}. Task {} -> {}", day.to_string().blue(), t.task().green(), result.to_string().yellow());
+    println!("Duration: {}", format!("{:?}", time.elapsed()).red());
+}
+
+fn run<O, R>(
+    day: u8,
+    t: Type,
+    parse: impl for<'s> Fn(&'s str) -> IResult<&'s str, O>,
+    solve: impl Fn(&mut O) -> R,
+) where
+    R: Display,
 {
     let time = Instant::now();
     let file_name = format!("examples/day{}/{}", day, t.file());
     let data = read_input(&file_name);
     let (remain, mut parsed) = match parse(&data) {
         Ok(pair) => pair,
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+            eprintln!("{}", parse_error(&data, 1, &err));
+            return;
+        }
         Err(err) => {
             eprintln!("{err}");
             return;
@@ -109,26 +223,150 @@ pub fn execute<O, R>(
     println!("Duration: {}", format!("{:?}", time.elapsed()).red());
 }
 
-pub fn window<'i, O, E: ParseError<&'i str>, F>(
-    size: usize,
+/// Describes a parse error with its line, column and the offending line of `data`.
+///
+/// Lines are counted from `first_line`, so a single line read from a bigger input keeps its number.
+fn parse_error(data: &str, first_line: usize, err: &nom::error::Error<&str>) -> String {
+    format_parse_error(data, first_line, err, true)
+}
+
+/// [`parse_error`] with the error code and position highlighted only when `colored` is set.
+fn format_parse_error(data: &str, first_line: usize, err: &nom::error::Error<&str>, colored: bool) -> String {
+    let offset = (err.input.as_ptr() as usize).wrapping_sub(data.as_ptr() as usize);
+    if offset > data.len() {
+        return format!("‼️ Parse error {:?} at `{}`", err.code, err.input);
+    }
+    let line_start = data[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = data[offset..].find(['\r', '\n']).map_or(data.len(), |i| offset + i);
+    let line = data[..offset].matches('\n').count() + first_line;
+    let column = data[line_start..offset].chars().count() + 1;
+    let code = format!("{:?}", err.code);
+    let position = format!("{line}:{column}");
+    let (code, position) = if colored {
+        (code.red().to_string(), position.blue().to_string())
+    } else {
+        (code, position)
+    };
+    format!(
+        "‼️ Parse error {code} at {position} ‼️\n{text}\n{caret:>column$}",
+        text = &data[line_start..line_end],
+        caret = "^",
+    )
+}
+
+/// Runs `parser` at every char boundary of `input` and collects all matches with their byte offset.
+///
+/// Matches may overlap: scanning `"twone"` for digit words yields `two` at 0 and `one` at 2.
+/// Consumes the whole input, no matches is an empty list.
+pub fn scan<'i, O, E: ParseError<&'i str>, F>(
     mut parser: F,
-) -> impl FnMut(&'i str) -> IResult<&'i str, O, E>
-    where F: Parser<&'i str, O, E>, O: 'i {
+) -> impl FnMut(&'i str) -> IResult<&'i str, Vec<(usize, O)>, E>
+    where F: Parser<&'i str, O, E> {
     move |input: &'i str| {
-        for start in 0..(input.len() - size) {
-            let end = start + size;
-            let result = parser.parse(&input[start..end]);
-            match result {
-                Ok((_, o)) => return Ok((input, o)),
+        let mut output = Vec::new();
+        for (position, _) in input.char_indices() {
+            match parser.parse(&input[position..]) {
+                Ok((_, o)) => output.push((position, o)),
                 Err(nom::Err::Error(_)) => continue,
-                Err(nom::Err::Failure(_)) => return result,
                 Err(nom::Err::Incomplete(_)) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok((&input[input.len()..], output))
+    }
+}
+
+/// Parses blank-line separated blocks of `input` with `parser`.
+///
+/// Every block is handed to `parser` on its own (including the line ending of its last line)
+/// and must be consumed up to trailing line endings. Leading and trailing blank lines are skipped,
+/// both `\n` and `\r\n` line endings are accepted.
+/// Stops at the first block `parser` fails on, like [`nom::multi::many1`].
+pub fn blocks<'i, O, E: ParseError<&'i str>, F>(
+    mut parser: F,
+) -> impl FnMut(&'i str) -> IResult<&'i str, Vec<O>, E>
+    where F: Parser<&'i str, O, E> {
+    move |input: &'i str| {
+        let mut output = Vec::new();
+        let mut input = skip_blank_lines(input);
+        while !input.is_empty() {
+            let (block, rest) = split_block(input);
+            match parse_block(&mut parser, block) {
+                Ok(o) => output.push(o),
+                Err(nom::Err::Error(err)) if output.is_empty() => return Err(nom::Err::Error(err)),
+                Err(nom::Err::Error(_)) => break,
+                Err(err) => return Err(err),
             }
+            input = rest;
         }
-        Err(nom::Err::Error(make_error(input, nom::error::ErrorKind::Fail)))
+        if output.is_empty() {
+            return Err(nom::Err::Error(make_error(input, nom::error::ErrorKind::Many1)));
+        }
+        Ok((input, output))
     }
 }
 
+/// Parses blank-line separated blocks which start with a header line.
+///
+/// `header` parses the first line of a block (without its line ending), `parser` the rest of it.
+/// Returns `(header, body)` pairs in input order, see [`blocks`] for the block rules.
+pub fn sections<'i, H, O, E: ParseError<&'i str>, P, F>(
+    mut header: P,
+    mut parser: F,
+) -> impl FnMut(&'i str) -> IResult<&'i str, Vec<(H, O)>, E>
+    where P: Parser<&'i str, H, E>, F: Parser<&'i str, O, E> {
+    blocks(move |input: &'i str| {
+        let (input, h) = header.parse(input)?;
+        let (input, _) = line_ending(input)?;
+        let (input, o) = parser.parse(input)?;
+        Ok((input, (h, o)))
+    })
+}
+
+/// Skips any number of blank lines, never fails.
+pub fn blank_lines(input: &str) -> IResult<&str, ()> {
+    Ok((skip_blank_lines(input), ()))
+}
+
+fn parse_block<'i, O, E: ParseError<&'i str>, F>(parser: &mut F, block: &'i str) -> Result<O, nom::Err<E>>
+    where F: Parser<&'i str, O, E> {
+    let (remain, output) = parser.parse(block)?;
+    if remain.trim_end_matches(['\r', '\n']).is_empty() {
+        Ok(output)
+    } else {
+        Err(nom::Err::Error(make_error(remain, nom::error::ErrorKind::Eof)))
+    }
+}
+
+/// Splits `input` before its first blank line.
+/// Returns the block (with the line ending of its last line) and the input after the blank lines.
+fn split_block(input: &str) -> (&str, &str) {
+    let mut end = 0;
+    for line in input.split_inclusive('\n') {
+        if is_blank(line) {
+            break;
+        }
+        end += line.len();
+    }
+    (&input[..end], skip_blank_lines(&input[end..]))
+}
+
+fn skip_blank_lines(input: &str) -> &str {
+    let mut start = 0;
+    for line in input.split_inclusive('\n') {
+        if !is_blank(line) {
+            break;
+        }
+        start += line.len();
+    }
+    &input[start..]
+}
+
+#[inline(always)]
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
 #[allow(non_snake_case)]
 pub fn nCk<N>(n: N, k: N) -> N where N: PrimInt + Unsigned + Product + Copy + Mul + Sub + Div {
     factorial(n) / (factorial(k) * factorial(n - k))
@@ -170,4 +408,111 @@ pub fn tracing(level: LevelFilter) {
         .with_max_level(level)
         .finish();
     let _ = tracing::subscriber::set_global_default(subscriber).is_ok();
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::branch::alt;
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{alpha1, line_ending};
+    use nom::IResult;
+    use nom::multi::separated_list1;
+    use nom::sequence::terminated;
+    use rstest::rstest;
+
+    use crate::{blocks, decimal, format_parse_error, labeled, labeled_id, labeled_map, numbers_list, scan, sections};
+    use crate::scanf::scan as scanf;
+
+    fn words(input: &str) -> IResult<&str, Vec<&str>> {
+        separated_list1(line_ending, alpha1)(input)
+    }
+
+    #[rstest]
+    #[case("ab\ncd\n\nef")]
+    #[case("ab\ncd\n\nef\n")]
+    #[case("\nab\ncd\n\n\nef\n\n")]
+    #[case("ab\r\ncd\r\n\r\nef\r\n")]
+    #[case("ab\ncd\n  \nef")]
+    fn check_blocks(#[case] input: &str) {
+        let (remain, output) = blocks(words)(input).expect("blocks parsed");
+        assert!(remain.is_empty(), "remained: `{remain}`");
+        assert_eq!(vec![vec!["ab", "cd"], vec!["ef"]], output);
+    }
+
+    #[test]
+    fn check_blocks_stop() {
+        let (remain, output) = blocks(words)("ab\n\n12\n").expect("blocks parsed");
+        assert_eq!("12\n", remain);
+        assert_eq!(vec![vec!["ab"]], output);
+        assert!(blocks(words)("ab 12\n\ncd").is_err(), "block is not fully parsed");
+    }
+
+    #[rstest]
+    #[case("first:\nab\ncd\n\nsecond:\nef\n")]
+    #[case("first:\r\nab\r\ncd\r\n\r\nsecond:\r\nef")]
+    fn check_sections(#[case] input: &str) {
+        let (remain, output) = sections(terminated(alpha1, tag(":")), words)(input).expect("sections parsed");
+        assert!(remain.is_empty(), "remained: `{remain}`");
+        assert_eq!(vec![("first", vec!["ab", "cd"]), ("second", vec!["ef"])], output);
+    }
+
+    #[test]
+    fn check_blocks_numbers() {
+        let (_, output) = blocks(decimal::<u32>)("1\n\n2\n\n3").expect("blocks parsed");
+        assert_eq!(vec![1, 2, 3], output);
+    }
+
+    #[rstest]
+    #[case("7 15 30")]
+    #[case("   7  15   30")]
+    #[case("7\t15 \t30\n")]
+    fn check_numbers_list(#[case] input: &str) {
+        let (_, output) = numbers_list::<u32>(input).expect("numbers parsed");
+        assert_eq!(vec![7, 15, 30], output);
+    }
+
+    #[test]
+    fn check_labeled() {
+        let (remain, output) = labeled("Time", numbers_list::<u32>)("Time:      7  15   30\n").expect("line parsed");
+        assert_eq!("\n", remain);
+        assert_eq!(vec![7, 15, 30], output);
+
+        let (remain, id) = labeled_id::<u32, nom::error::Error<_>>("Card")("Card  12: 41 48").expect("id parsed");
+        assert_eq!("41 48", remain);
+        assert_eq!(12, id);
+    }
+
+    #[test]
+    fn check_labeled_map() {
+        let input = "Distance:  9  40  200\r\nTime:      7  15   30\n";
+        let (remain, output) = labeled_map(numbers_list::<u32>)(input).expect("fields parsed");
+        assert!(remain.is_empty(), "remained: `{remain}`");
+        assert_eq!(Some(&vec![7, 15, 30]), output.get("Time"));
+        assert_eq!(Some(&vec![9, 40, 200]), output.get("Distance"));
+    }
+
+    #[rstest]
+    #[case("twone", vec![(0, "two"), (2, "one")])]
+    #[case("eightwo", vec![(0, "eight"), (4, "two")])]
+    #[case("tw", vec![])]
+    #[case("", vec![])]
+    #[case("ñtwoé one", vec![(2, "two"), (8, "one")])]
+    #[case("xone", vec![(1, "one")])]
+    fn check_scan(#[case] input: &str, #[case] expected: Vec<(usize, &str)>) {
+        let mut parser = scan::<_, nom::error::Error<_>, _>(alt((tag("one"), tag("two"), tag("eight"))));
+        let (remain, output) = parser(input).expect("input scanned");
+        assert!(remain.is_empty(), "remained: `{remain}`");
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn check_parse_error() {
+        let data = "AAA = (BBB, CCC)\nBBB = [DDD, EEE)\n";
+        let second_line = &data[data.find('\n').unwrap() + 1..];
+        let err = scanf!("{} = ({}, {})", String, String, String)(second_line).expect_err("line is broken");
+        let nom::Err::Error(err) = err else { panic!("unexpected {err:?}") };
+        let message = format_parse_error(data, 1, &err, false);
+        assert!(message.contains("Tag at 2:7"), "{message}");
+        assert!(message.ends_with("BBB = [DDD, EEE)\n      ^"), "{message}");
+    }
+}
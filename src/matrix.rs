@@ -1,13 +1,61 @@
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
-use std::ops::IndexMut;
+use std::iter::StepBy;
+use std::ops::{Add, AddAssign, IndexMut, Mul, Neg, Sub, SubAssign};
+use std::slice;
 use itertools::Itertools;
 
+pub use animation::Animation;
+pub use bit_grid::BitGrid;
+pub use fingerprint::Fingerprint;
+pub use image::{DEFAULT_SCALE, Image, Palette, Rgb};
+pub use render::{Align, CellRenderer, Chars, Displayed, Layer, Rendered};
+pub use resize::{Rect, SubMatrix};
+pub use text::TextError;
+pub use transform::{Transform, View};
+pub use wrapping::Wrapping;
+
+mod algebra;
+mod animation;
+mod bit_grid;
+mod fingerprint;
+mod image;
+mod iter;
+mod render;
+mod resize;
+mod text;
+mod transform;
+mod wrapping;
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawMatrix<T>"))]
 pub struct Matrix<T> {
     inner: Vec<T>,
     size: Size,
 }
 
+/// Deserialized matrix fields, checked before they become a [`Matrix`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawMatrix<T> {
+    inner: Vec<T>,
+    size: Size,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<RawMatrix<T>> for Matrix<T> {
+    type Error = String;
+
+    fn try_from(RawMatrix { inner, size }: RawMatrix<T>) -> Result<Self, Self::Error> {
+        if inner.len() == size.area() {
+            Ok(Self { inner, size })
+        } else {
+            Err(format!("{} cells do not fill {size} matrix", inner.len()))
+        }
+    }
+}
+
 impl<T> Matrix<T> where T: Default + Clone {
     pub fn new(size: Size) -> Self {
         let inner = vec![T::default(); size.width() * size.height()];
@@ -15,33 +63,91 @@ impl<T> Matrix<T> where T: Default + Clone {
     }
 }
 
+impl<T> Matrix<T> where T: Clone {
+    /// Matrix of `size` with every cell set to `value`.
+    pub fn filled(size: Size, value: T) -> Self {
+        Self { inner: vec![value; size.area()], size }
+    }
+}
+
 impl<T> Matrix<T> {
     pub fn get(&self, index: Index) -> &T {
         let index = self.index(index);
-        self.inner.get(index).unwrap()
+        &self.inner[index]
     }
 
     pub fn get_at(&self, x: usize, y: usize) -> &T {
         let index = self.index_xy(x, y);
-        self.inner.get(index).unwrap()
+        &self.inner[index]
     }
 
     pub fn set(&mut self, index: Index, value: T) {
         let index = self.index(index);
-        *self.inner.get_mut(index).unwrap() = value
+        self.inner[index] = value
     }
 
     pub fn set_at(&mut self, x: usize, y: usize, value: T) {
         let index = self.index_xy(x, y);
-        *self.inner.get_mut(index).unwrap() = value
+        self.inner[index] = value
+    }
+
+    /// Cell at `index`, `None` if it is outside the matrix.
+    pub fn try_get(&self, index: Index) -> Option<&T> {
+        self.try_get_at(index.x(), index.y())
+    }
+
+    pub fn try_get_at(&self, x: usize, y: usize) -> Option<&T> {
+        self.checked(x, y).map(|index| self.get(index))
+    }
+
+    pub fn get_mut(&mut self, index: Index) -> Option<&mut T> {
+        if self.contains(index) {
+            let index = self.index(index);
+            Some(&mut self.inner[index])
+        } else {
+            None
+        }
+    }
+
+    /// Index of `(x, y)` if it is inside the matrix.
+    ///
+    /// Coordinates computed with `wrapping_sub` at the edge are rejected as well.
+    pub fn checked(&self, x: usize, y: usize) -> Option<Index> {
+        let index = Index(x, y);
+        self.contains(index).then_some(index)
+    }
+
+    pub fn contains(&self, index: Index) -> bool {
+        self.size.contains(index)
     }
 
     pub fn size(&self) -> Size {
         self.size
     }
 
+    /// Side neighbors of `index` which are inside the matrix, clockwise from north.
+    pub fn neighbors4(&self, index: Index) -> impl Iterator<Item=Index> {
+        let size = self.size;
+        Direction::ALL.into_iter().filter_map(move |direction| index.step_within(direction, size))
+    }
+
+    /// Side and corner neighbors of `index` which are inside the matrix, clockwise from north.
+    pub fn neighbors8(&self, index: Index) -> impl Iterator<Item=Index> {
+        let size = self.size;
+        Direction8::ALL.into_iter().filter_map(move |direction| index.step_within(direction, size))
+    }
+
+    /// Indexes from the neighbor of `index` in `direction` up to the edge of the matrix.
+    pub fn ray(&self, index: Index, direction: impl Into<Offset>) -> impl Iterator<Item=Index> {
+        let size = self.size;
+        let offset = direction.into();
+        std::iter::successors(index.step_within(offset, size), move |index| index.step_within(offset, size))
+    }
+
+    /// Panics if any axis is out of bounds, so `x >= width` never reads the next row.
     #[inline(always)]
     fn index_xy(&self, x: usize, y: usize) -> usize {
+        assert!(self.size.contains(Index(x, y)), "Index ({x}, {y}) is out of bounds of {} matrix", self.size);
         self.size.width() * y + x
     }
 
@@ -56,8 +162,7 @@ impl<T> Display for Matrix<T> where T: Display {
         for y in 0..self.size.height() {
             write!(f, "|| ")?;
             for x in 0..self.size.width() {
-                let index = self.index_xy(x, y);
-                let value = self.inner.get(index).unwrap();
+                let value = self.get_at(x, y);
                 write!(f, "{value} |")?;
             }
             writeln!(f, "|")?;
@@ -67,10 +172,18 @@ impl<T> Display for Matrix<T> where T: Display {
 }
 
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Cell coordinate, `x` is the column and `y` is the row.
+///
+/// Ordered row by row, the same order cells are stored and printed in.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Index(usize, usize);
 
 impl Index {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self(x, y)
+    }
+
     #[inline(always)]
     pub fn x(&self) -> usize {
         self.0
@@ -80,12 +193,275 @@ impl Index {
     pub fn y(&self) -> usize {
         self.1
     }
+
+    /// `self + offset`, `None` if any coordinate would become negative or overflow.
+    pub fn checked_add(&self, offset: Offset) -> Option<Index> {
+        let x = self.0.checked_add_signed(offset.dx())?;
+        let y = self.1.checked_add_signed(offset.dy())?;
+        Some(Self(x, y))
+    }
+
+    /// Neighbor in `direction`, `None` when stepping off the zero edge.
+    ///
+    /// Takes a [`Direction`], a [`Direction8`] or any [`Offset`].
+    pub fn step(&self, direction: impl Into<Offset>) -> Option<Index> {
+        self.checked_add(direction.into())
+    }
+
+    /// Neighbor in `direction` that stays inside of `size`.
+    pub fn step_within(&self, direction: impl Into<Offset>, size: Size) -> Option<Index> {
+        self.step(direction).filter(|index| size.contains(*index))
+    }
+
+    pub fn manhattan(&self, other: Index) -> usize {
+        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
+    }
+}
+
+impl Ord for Index {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.1, self.0).cmp(&(other.1, other.0))
+    }
+}
+
+impl PartialOrd for Index {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Index {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+impl From<(usize, usize)> for Index {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self(x, y)
+    }
+}
+
+impl From<Index> for (usize, usize) {
+    fn from(index: Index) -> Self {
+        (index.0, index.1)
+    }
+}
+
+impl Add<Offset> for Index {
+    type Output = Index;
+
+    /// Panics if the result is out of `usize`, use [`Index::checked_add`] at the edges.
+    fn add(self, offset: Offset) -> Self::Output {
+        self.checked_add(offset)
+            .unwrap_or_else(|| panic!("Index {self} moved by {offset:?} is out of range"))
+    }
+}
+
+impl Sub<Offset> for Index {
+    type Output = Index;
+
+    fn sub(self, offset: Offset) -> Self::Output {
+        self + -offset
+    }
+}
+
+impl AddAssign<Offset> for Index {
+    fn add_assign(&mut self, offset: Offset) {
+        *self = *self + offset
+    }
+}
+
+impl SubAssign<Offset> for Index {
+    fn sub_assign(&mut self, offset: Offset) {
+        *self = *self - offset
+    }
+}
+
+impl Sub for Index {
+    type Output = Offset;
+
+    /// Signed delta which moves `other` to `self`.
+    fn sub(self, other: Index) -> Self::Output {
+        Offset(self.0 as isize - other.0 as isize, self.1 as isize - other.1 as isize)
+    }
+}
+
+/// Signed distance between two [`Index`]es.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Offset(isize, isize);
+
+impl Offset {
+    pub const fn new(dx: isize, dy: isize) -> Self {
+        Self(dx, dy)
+    }
+
+    #[inline(always)]
+    pub fn dx(&self) -> isize {
+        self.0
+    }
+
+    #[inline(always)]
+    pub fn dy(&self) -> isize {
+        self.1
+    }
+}
+
+impl From<(isize, isize)> for Offset {
+    fn from((dx, dy): (isize, isize)) -> Self {
+        Self(dx, dy)
+    }
+}
+
+impl Add for Offset {
+    type Output = Offset;
+
+    fn add(self, other: Offset) -> Self::Output {
+        Offset(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl Sub for Offset {
+    type Output = Offset;
+
+    fn sub(self, other: Offset) -> Self::Output {
+        Offset(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+impl Neg for Offset {
+    type Output = Offset;
+
+    fn neg(self) -> Self::Output {
+        Offset(-self.0, -self.1)
+    }
+}
+
+impl Mul<isize> for Offset {
+    type Output = Offset;
+
+    fn mul(self, times: isize) -> Self::Output {
+        Offset(self.0 * times, self.1 * times)
+    }
+}
+
+/// Side of a cell, `North` is towards `y = 0`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise, starting from `North`.
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    pub fn offset(&self) -> Offset {
+        match self {
+            Direction::North => Offset(0, -1),
+            Direction::East => Offset(1, 0),
+            Direction::South => Offset(0, 1),
+            Direction::West => Offset(-1, 0),
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// 90° counterclockwise.
+    pub fn turn_left(&self) -> Self {
+        Self::ALL[(*self as usize + 3) % 4]
+    }
+
+    /// 90° clockwise.
+    pub fn turn_right(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % 4]
+    }
+}
+
+impl From<Direction> for Offset {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+/// Side or corner of a cell, for neighborhoods that include diagonals.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Clockwise, starting from `North`.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North, Direction8::NorthEast, Direction8::East, Direction8::SouthEast,
+        Direction8::South, Direction8::SouthWest, Direction8::West, Direction8::NorthWest,
+    ];
+
+    pub fn offset(&self) -> Offset {
+        match self {
+            Direction8::North => Offset(0, -1),
+            Direction8::NorthEast => Offset(1, -1),
+            Direction8::East => Offset(1, 0),
+            Direction8::SouthEast => Offset(1, 1),
+            Direction8::South => Offset(0, 1),
+            Direction8::SouthWest => Offset(-1, 1),
+            Direction8::West => Offset(-1, 0),
+            Direction8::NorthWest => Offset(-1, -1),
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        Self::ALL[(*self as usize + 4) % 8]
+    }
+
+    /// 45° counterclockwise.
+    pub fn turn_left(&self) -> Self {
+        Self::ALL[(*self as usize + 7) % 8]
+    }
+
+    /// 45° clockwise.
+    pub fn turn_right(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % 8]
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        *self as usize % 2 == 1
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+impl From<Direction8> for Offset {
+    fn from(direction: Direction8) -> Self {
+        direction.offset()
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Size(usize, usize);
 
 impl Size {
+    pub const fn new(width: usize, height: usize) -> Self {
+        Self(width, height)
+    }
+
     #[inline(always)]
     pub fn width(&self) -> usize {
         self.0
@@ -95,6 +471,33 @@ impl Size {
     pub fn height(&self) -> usize {
         self.1
     }
+
+    /// Number of cells.
+    pub fn area(&self) -> usize {
+        self.0 * self.1
+    }
+
+    pub fn contains(&self, index: Index) -> bool {
+        index.x() < self.0 && index.y() < self.1
+    }
+}
+
+impl Display for Size {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}", self.0, self.1)
+    }
+}
+
+impl From<(usize, usize)> for Size {
+    fn from((width, height): (usize, usize)) -> Self {
+        Self(width, height)
+    }
+}
+
+impl From<Size> for (usize, usize) {
+    fn from(size: Size) -> Self {
+        (size.0, size.1)
+    }
 }
 
 impl<T> From<Vec<Vec<T>>> for Matrix<T> {
@@ -126,19 +529,21 @@ impl<T> std::ops::Index<Index> for Matrix<T> {
     type Output = T;
 
     fn index(&self, index: Index) -> &Self::Output {
-        let index = self.size.width() * index.y() + index.x();
-        self.inner.get(index).unwrap()
+        self.get(index)
     }
 }
 
 impl<T> IndexMut<Index> for Matrix<T> {
     fn index_mut(&mut self, index: Index) -> &mut Self::Output {
-        let index = self.size.width() * index.y() + index.x();
-        self.inner.get_mut(index).unwrap()
+        let index = self.index(index);
+        &mut self.inner[index]
     }
 }
 
 
+/// Iterator over the cells of a [`Column`], stepping one row at a time.
+pub type ColumnIter<'a, T> = StepBy<slice::Iter<'a, T>>;
+
 #[derive(Debug, Clone, Copy)]
 pub struct Column<'a, T> {
     data: &'a Matrix<T>,
@@ -159,32 +564,36 @@ impl<'a, T> Column<'a, T> {
         self.height
     }
 
-    pub fn iter(&self) -> impl Iterator<Item=&T> {
-        let height = self.height;
-        let mut index = 0usize;
-        std::iter::from_fn(move || {
-            let next = if index < height {
-                Some(&self[index])
-            } else {
-                None
-            };
-            index += 1;
-            next
-        })
+    pub fn is_empty(&self) -> bool {
+        self.height == 0
+    }
+
+    pub fn iter(&self) -> ColumnIter<'a, T> {
+        self.data.inner.get(self.column..).unwrap_or(&[]).iter().step_by(self.data.size.width())
+    }
+}
+
+impl<'a, T> IntoIterator for Column<'a, T> {
+    type Item = &'a T;
+    type IntoIter = ColumnIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &Column<'a, T> {
+    type Item = &'a T;
+    type IntoIter = ColumnIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
 impl<'a, T> PartialEq for Column<'a, T> where T: PartialEq {
     fn eq(&self, other: &Self) -> bool {
-        if self.height != other.height {
-            return false;
-        }
-        for i in 0..self.height {
-            if self[i] != other[i] {
-                return false;
-            }
-        }
-        return true;
+        self.iter().eq(other.iter())
     }
 }
 
@@ -199,6 +608,74 @@ impl<T> std::ops::Index<usize> for Column<'_, T> {
 }
 
 
+/// Mutable column, a strided view over the cells of one `x`.
+#[derive(Debug)]
+pub struct ColumnMut<'a, T> {
+    data: &'a mut [T],
+    column: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> ColumnMut<'a, T> {
+    pub fn x(&self) -> usize {
+        self.column
+    }
+
+    pub fn len(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.height == 0
+    }
+
+    pub fn iter(&self) -> ColumnIter<'_, T> {
+        self.data.iter().step_by(self.width)
+    }
+
+    pub fn iter_mut(&mut self) -> StepBy<slice::IterMut<'_, T>> {
+        self.data.iter_mut().step_by(self.width)
+    }
+
+    /// Swaps the cells at rows `a` and `b`.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.offset(a), self.offset(b));
+        self.data.swap(a, b)
+    }
+
+    pub fn fill(&mut self, value: T) where T: Clone {
+        self.iter_mut().for_each(|cell| *cell = value.clone())
+    }
+
+    /// Copies `values` top to bottom, their number has to match the height.
+    pub fn copy_from(&mut self, values: &[T]) where T: Clone {
+        assert_eq!(self.height, values.len(), "Column height mismatch");
+        self.iter_mut().zip(values).for_each(|(cell, value)| *cell = value.clone())
+    }
+
+    fn offset(&self, y: usize) -> usize {
+        assert!(y < self.height, "Row {y} is out of bounds of column with height {}", self.height);
+        y * self.width
+    }
+}
+
+impl<T> std::ops::Index<usize> for ColumnMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.data[self.offset(index)]
+    }
+}
+
+impl<T> IndexMut<usize> for ColumnMut<'_, T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let offset = self.offset(index);
+        &mut self.data[offset]
+    }
+}
+
+
 #[derive(Debug, Clone, Copy)]
 pub struct Row<'a, T> {
     data: &'a Matrix<T>,
@@ -219,33 +696,40 @@ impl<'a, T> Row<'a, T> {
         self.width
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.width == 0
+    }
 
-    pub fn iter(&self) -> impl Iterator<Item=&T> {
-        let width = self.width;
-        let mut index = 0usize;
-        std::iter::from_fn(move || {
-            let next = if index < width {
-                Some(&self[index])
-            } else {
-                None
-            };
-            index += 1;
-            next
-        })
+    pub fn as_slice(&self) -> &'a [T] {
+        self.data.row_slice(self.row)
+    }
+
+    pub fn iter(&self) -> slice::Iter<'a, T> {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a, T> IntoIterator for Row<'a, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &Row<'a, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
 impl<'a, T> PartialEq for Row<'a, T> where T: PartialEq {
     fn eq(&self, other: &Self) -> bool {
-        if self.width != other.width {
-            return false;
-        }
-        for i in 0..self.width {
-            if self[i] != other[i] {
-                return false;
-            }
-        }
-        return true;
+        self.as_slice() == other.as_slice()
     }
 }
 
@@ -256,7 +740,7 @@ impl<T> std::ops::Index<usize> for Row<'_, T> {
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
-        self.data.get_at(index, self.row)
+        &self.as_slice()[index]
     }
 }
 
@@ -267,6 +751,13 @@ impl<T> Matrix<T> {
         Column::new(self, x, self.size.height())
     }
 
+    pub fn column_mut(&mut self, x: usize) -> ColumnMut<'_, T> {
+        assert!(x < self.size.width());
+        let Size(width, height) = self.size;
+        let data = self.inner.get_mut(x..).unwrap_or(&mut []);
+        ColumnMut { data, column: x, width, height }
+    }
+
     pub fn columns(&self) -> impl Iterator<Item=Column<'_, T>> {
         let width = self.size.width();
         let mut index = 0usize;
@@ -286,6 +777,18 @@ impl<T> Matrix<T> {
         Row::new(self, y, self.size.width())
     }
 
+    /// Cells of row `y`, rows are stored contiguously.
+    pub fn row_slice(&self, y: usize) -> &[T] {
+        assert!(y < self.size.height());
+        let width = self.size.width();
+        &self.inner[y * width..(y + 1) * width]
+    }
+
+    pub fn row_slice_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.size.height());
+        let width = self.size.width();
+        &mut self.inner[y * width..(y + 1) * width]
+    }
 
     pub fn rows(&self) -> impl Iterator<Item=Row<'_, T>> {
         let height = self.size.height();
@@ -301,3 +804,124 @@ impl<T> Matrix<T> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::{Direction, Direction8, Index, Matrix, Offset, Size};
+
+    #[test]
+    fn check_checked_access() {
+        let mut matrix = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(Some(&6), matrix.try_get_at(2, 1));
+        assert_eq!(None, matrix.try_get_at(3, 0));
+        assert_eq!(None, matrix.try_get_at(0, 2));
+        assert_eq!(None, matrix.checked(0usize.wrapping_sub(1), 0));
+        assert!(matrix.contains(Index(2, 1)));
+        assert!(!matrix.contains(Index(1, 2)));
+
+        *matrix.get_mut(Index(1, 0)).expect("inside") = 7;
+        assert_eq!(&7, matrix.get_at(1, 0));
+        assert!(matrix.get_mut(Index(3, 1)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Index (3, 0) is out of bounds of 3x2 matrix")]
+    fn check_x_out_of_bounds() {
+        Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]).get_at(3, 0);
+    }
+
+    #[test]
+    fn check_index_algebra() {
+        let index = Index::new(3, 1);
+        assert_eq!(Index::new(4, 3), index + Offset::new(1, 2));
+        assert_eq!(Index::new(2, 1), index - Offset::new(1, 0));
+        assert_eq!(Offset::new(-2, 1), Index::new(1, 2) - index);
+        assert_eq!(None, index.checked_add(Offset::new(0, -2)));
+        assert_eq!(5, index.manhattan(Index::new(0, 3)));
+        assert_eq!((3, 1), index.into());
+        assert_eq!(Index::from((3, 1)), index);
+
+        let mut indexes = vec![Index::new(0, 1), Index::new(2, 0), Index::new(1, 1)];
+        indexes.sort();
+        assert_eq!(vec![Index::new(2, 0), Index::new(0, 1), Index::new(1, 1)], indexes);
+    }
+
+    #[test]
+    fn check_step() {
+        let size = Size::new(4, 2);
+        let corner = Index::new(3, 0);
+        assert_eq!(None, corner.step(Direction::North));
+        assert_eq!(Some(Index::new(4, 0)), corner.step(Direction::East));
+        assert_eq!(None, corner.step_within(Direction::East, size));
+        assert_eq!(Some(Index::new(3, 1)), corner.step_within(Direction::South, size));
+        assert_eq!(Some(Index::new(2, 0)), corner.step_within(Direction::West, size));
+    }
+
+    #[test]
+    fn check_directions() {
+        assert_eq!(Direction::South, Direction::North.opposite());
+        assert_eq!(Direction::West, Direction::North.turn_left());
+        assert_eq!(Direction::North, Direction::West.turn_right());
+        assert_eq!(Direction8::SouthWest, Direction8::NorthEast.opposite());
+        assert_eq!(Direction8::NorthWest, Direction8::North.turn_left());
+        assert_eq!(Direction8::from(Direction::West), Direction8::SouthWest.turn_right());
+        assert!(Direction8::SouthEast.is_diagonal());
+    }
+
+    #[test]
+    fn check_neighbors() {
+        let matrix = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let corner = matrix.neighbors4(Index::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(vec![Index::new(1, 0), Index::new(0, 1)], corner);
+        let middle = matrix.neighbors8(Index::new(1, 1)).collect::<Vec<_>>();
+        let expected = [(1, 0), (2, 0), (2, 1), (0, 1), (0, 0)].map(Index::from);
+        assert_eq!(expected.to_vec(), middle);
+    }
+
+    #[test]
+    fn check_ray() {
+        let matrix = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let east = matrix.ray(Index::new(0, 1), Direction::East).map(|index| matrix[index]).collect::<Vec<_>>();
+        assert_eq!(vec![5, 6], east);
+        assert_eq!(0, matrix.ray(Index::new(2, 0), Direction8::NorthEast).count());
+        let diagonal = matrix.ray(Index::new(0, 0), Direction8::SouthEast).collect::<Vec<_>>();
+        assert_eq!(vec![Index::new(1, 1)], diagonal);
+    }
+
+    #[test]
+    fn check_rows_and_columns() {
+        let matrix = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(&[4, 5, 6], matrix.row_slice(1));
+        let row = matrix.row(0);
+        assert_eq!(vec![3, 2, 1], row.iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!(3, row.iter().len());
+        let column = matrix.column(2);
+        assert_eq!(vec![&6, &3], column.iter().rev().collect::<Vec<_>>());
+        assert_eq!(2, column.iter().len());
+        assert_eq!(9, column.into_iter().sum::<u32>());
+        assert_eq!(vec![&4, &5, &6], matrix.row(1).into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn check_empty_columns() {
+        let mut matrix = Matrix::filled(Size::new(3, 0), 0);
+        assert!(matrix.column(2).is_empty());
+        assert_eq!(0, matrix.column(2).iter().count());
+        assert_eq!(3, matrix.columns().count());
+        matrix.column_mut(2).fill(1);
+        assert_eq!(0, matrix.column_mut(1).iter_mut().count());
+    }
+
+    #[test]
+    fn check_mutable_views() {
+        let mut matrix = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        matrix.row_slice_mut(0).reverse();
+        let mut column = matrix.column_mut(1);
+        column.swap(0, 1);
+        column[1] += 10;
+        assert_eq!(vec![5, 12], column.iter().copied().collect::<Vec<_>>());
+        matrix.column_mut(0).fill(0);
+        matrix.column_mut(2).copy_from(&[7, 8]);
+        assert_eq!(Matrix::from(vec![vec![0, 5, 7], vec![0, 12, 8]]), matrix);
+    }
+}
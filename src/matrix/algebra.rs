@@ -0,0 +1,208 @@
+use std::ops::{Add, Mul};
+
+use num::{NumCast, One, PrimInt, Zero};
+
+use crate::matrix::{Matrix, Size};
+
+impl<T> Matrix<T> where T: Clone + Zero + One {
+    /// `n`×`n` matrix with ones on the diagonal.
+    pub fn identity(n: usize) -> Self {
+        let mut matrix = Matrix::filled(Size(n, n), T::zero());
+        for i in 0..n {
+            matrix.set_at(i, i, T::one());
+        }
+        matrix
+    }
+}
+
+impl<T> Matrix<T> where T: Clone + Zero + One + Add<Output=T> + Mul<Output=T> {
+    /// `self` multiplied by itself `k` times by repeated squaring, `O(n³ log k)`.
+    pub fn pow(&self, k: u64) -> Self {
+        self.assert_square();
+        power(self, k, Self::identity(self.size.width()), |a, b| a * b)
+    }
+}
+
+impl<T> Matrix<T> where T: PrimInt {
+    /// Product with every cell taken modulo `modulus`, any positive `modulus` of `T` works.
+    ///
+    /// Cells are reduced into `0..modulus` first, so negative cells give non-negative results.
+    pub fn mul_mod(&self, other: &Matrix<T>, modulus: T) -> Self {
+        assert!(modulus > T::zero(), "Modulus must be positive");
+        let wide_modulus = wide(modulus);
+        multiply(self, other, 0, |a, b| add_mod(a, b, wide_modulus), |a, b| {
+            mul_mod(reduced(a, modulus), reduced(b, modulus), wide_modulus)
+        }).map(|cell| NumCast::from(*cell).unwrap())
+    }
+
+    /// `self` to the power of `k` modulo `modulus`, see [`Matrix::mul_mod`].
+    pub fn pow_mod(&self, k: u64, modulus: T) -> Self {
+        assert!(modulus > T::zero(), "Modulus must be positive");
+        self.assert_square();
+        let identity = Self::identity(self.size.width()).map(|cell| *cell % modulus);
+        power(self, k, identity, |a, b| a.mul_mod(b, modulus))
+    }
+}
+
+impl<T> Matrix<T> {
+    fn assert_square(&self) {
+        assert_eq!(self.size.width(), self.size.height(), "{} matrix is not square", self.size);
+    }
+}
+
+fn wide<T: PrimInt>(value: T) -> u128 {
+    NumCast::from(value).expect("non-negative value fits into u128")
+}
+
+/// `value` moved into `0..modulus` and widened.
+fn reduced<T: PrimInt>(value: T, modulus: T) -> u128 {
+    let rem = value % modulus;
+    wide(if rem < T::zero() { rem + modulus } else { rem })
+}
+
+/// `a + b` modulo `modulus` for `a` and `b` below it, without overflowing `u128`.
+fn add_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    if a >= modulus - b { a - (modulus - b) } else { a + b }
+}
+
+/// `a * b` modulo `modulus` for `a` and `b` below it, doubling and adding when the product overflows.
+fn mul_mod(mut a: u128, mut b: u128, modulus: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b >>= 1;
+    }
+    result
+}
+
+fn power<T: Clone>(base: &Matrix<T>, mut k: u64, identity: Matrix<T>, mul: impl Fn(&Matrix<T>, &Matrix<T>) -> Matrix<T>) -> Matrix<T> {
+    let mut result = identity;
+    let mut base = base.clone();
+    while k > 0 {
+        if k & 1 == 1 {
+            result = mul(&result, &base);
+        }
+        k >>= 1;
+        if k > 0 {
+            base = mul(&base, &base);
+        }
+    }
+    result
+}
+
+/// Row by column product with custom cell `add` and `mul`, sums start from `zero`.
+fn multiply<T, U: Clone>(a: &Matrix<T>, b: &Matrix<T>, zero: U, add: impl Fn(U, U) -> U, mul: impl Fn(T, T) -> U) -> Matrix<U>
+    where T: Clone {
+    assert_eq!(a.size.width(), b.size.height(), "Can't multiply {} matrix by {} matrix", a.size, b.size);
+    let size = Size(b.size.width(), a.size.height());
+    let inner = (0..size.area())
+        .map(|i| {
+            let (x, y) = (i % size.width(), i / size.width());
+            a.row_slice(y).iter()
+                .zip(b.column(x).iter())
+                .map(|(a, b)| mul(a.clone(), b.clone()))
+                .fold(zero.clone(), &add)
+        })
+        .collect();
+    Matrix { inner, size }
+}
+
+impl<T> Add for &Matrix<T> where T: Clone + Add<Output=T> {
+    type Output = Matrix<T>;
+
+    fn add(self, other: &Matrix<T>) -> Self::Output {
+        assert_eq!(self.size, other.size, "Can't add {} matrix to {} matrix", other.size, self.size);
+        let inner = self.inner.iter()
+            .zip(&other.inner)
+            .map(|(a, b)| a.clone() + b.clone())
+            .collect();
+        Matrix { inner, size: self.size }
+    }
+}
+
+impl<T> Add for Matrix<T> where T: Clone + Add<Output=T> {
+    type Output = Matrix<T>;
+
+    fn add(self, other: Matrix<T>) -> Self::Output {
+        &self + &other
+    }
+}
+
+impl<T> Mul for &Matrix<T> where T: Clone + Zero + Mul<Output=T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, other: &Matrix<T>) -> Self::Output {
+        multiply(self, other, T::zero(), |a, b| a + b, |a, b| a * b)
+    }
+}
+
+impl<T> Mul for Matrix<T> where T: Clone + Zero + Mul<Output=T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, other: Matrix<T>) -> Self::Output {
+        &self * &other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+
+    use crate::matrix::{Matrix, Size};
+
+    fn fibonacci<T: Clone>(one: T, zero: T) -> Matrix<T> {
+        vec![vec![one.clone(), one.clone()], vec![one, zero]].into()
+    }
+
+    #[test]
+    fn check_arithmetic() {
+        let a = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::from(vec![vec![1, 0], vec![0, 1], vec![2, -1]]);
+        assert_eq!(Matrix::from(vec![vec![7, -1], vec![16, -1]]), &a * &b);
+        assert_eq!(Matrix::from(vec![vec![2, 4, 6], vec![8, 10, 12]]), a.clone() + a.clone());
+        assert_eq!(a, Matrix::identity(2) * a.clone());
+        let empty = &Matrix::<i32>::filled(Size::new(0, 2), 0) * &Matrix::filled(Size::new(3, 0), 0);
+        assert_eq!(Matrix::filled(Size::new(3, 2), 0), empty);
+        let empty = Matrix::<u64>::filled(Size::new(0, 2), 0).mul_mod(&Matrix::filled(Size::new(3, 0), 0), 7);
+        assert_eq!(Matrix::filled(Size::new(3, 2), 0), empty);
+    }
+
+    #[test]
+    fn check_pow() {
+        assert_eq!(Matrix::identity(2), fibonacci(1u64, 0).pow(0));
+        assert_eq!(&55, fibonacci(1u64, 0).pow(10).get_at(1, 0));
+        let big = fibonacci(BigInt::from(1), BigInt::from(0)).pow(200);
+        assert_eq!("280571172992510140037611932413038677189525", big.get_at(1, 0).to_string());
+    }
+
+    #[test]
+    fn check_pow_mod() {
+        let modulus = 1_000_000_007u64;
+        let fib = fibonacci(1u64, 0).pow_mod(1_000_000_000_000, modulus);
+        assert_eq!(&730_695_249, fib.get_at(1, 0));
+        let negative = Matrix::from(vec![vec![-1i64, 0], vec![0, -1]]).pow_mod(3, 5);
+        assert_eq!(Matrix::from(vec![vec![4, 0], vec![0, 4]]), negative);
+    }
+
+    #[test]
+    #[should_panic(expected = "Modulus must be positive")]
+    fn check_pow_mod_zero_modulus() {
+        Matrix::from(vec![vec![1u64]]).pow_mod(0, 0);
+    }
+
+    #[test]
+    fn check_mul_mod_large_modulus() {
+        let m = Matrix::from(vec![vec![u64::MAX - 1]]);
+        assert_eq!(Matrix::from(vec![vec![1]]), m.mul_mod(&m, u64::MAX));
+        let m = Matrix::from(vec![vec![u128::MAX - 1]]);
+        assert_eq!(Matrix::from(vec![vec![1]]), m.mul_mod(&m, u128::MAX));
+        let fib = fibonacci(1u64, 0).pow_mod(1_000_000_000_000, u64::MAX);
+        assert_eq!(&11_409_513_574_017_346_965, fib.get_at(1, 0));
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Write};
+use std::path::Path;
+
+use crate::matrix::{Image, Matrix, Palette, Rgb, Size};
+
+/// Frames collected during a simulation, written as a looping animated GIF.
+///
+/// All frames share one color table, so at most 256 distinct colors are allowed.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    frames: Vec<Image>,
+    scale: usize,
+    delay: u16,
+}
+
+impl Animation {
+    /// Frames recorded from matrices use `scale`×`scale` pixels per cell.
+    pub fn new(scale: usize) -> Self {
+        Self { frames: Vec::new(), scale, delay: 10 }
+    }
+
+    /// Time every frame is shown, in hundredths of a second.
+    pub fn delay(mut self, centiseconds: u16) -> Self {
+        self.delay = centiseconds;
+        self
+    }
+
+    pub fn record<T>(&mut self, matrix: &Matrix<T>, palette: &impl Palette<T>) {
+        self.push(matrix.to_image(palette, self.scale));
+    }
+
+    pub fn push(&mut self, frame: Image) {
+        if let Some(first) = self.frames.first() {
+            assert_eq!(first.size(), frame.size(), "Frame size differs from the first frame");
+        }
+        self.frames.push(frame);
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write_gif(&mut out)?;
+        out.flush()
+    }
+
+    pub fn write_gif(&self, mut out: impl Write) -> std::io::Result<()> {
+        let size = self.frames.first().map_or(Size(0, 0), Image::size);
+        if size.width() > u16::MAX as usize || size.height() > u16::MAX as usize {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("{size} animation is too large for GIF")));
+        }
+        let mut colors = Vec::<Rgb>::new();
+        let mut color_index = HashMap::new();
+        let frames = self.frames.iter()
+            .map(|frame| frame.pixels().iter()
+                .map(|&color| *color_index.entry(color).or_insert_with(|| {
+                    colors.push(color);
+                    colors.len() - 1
+                }))
+                .collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        if colors.len() > 256 {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("{} colors do not fit a GIF palette", colors.len())));
+        }
+        let bits = (1..=8).find(|bits| colors.len() <= 1 << bits).unwrap_or(8);
+        let (width, height) = ((size.width() as u16).to_le_bytes(), (size.height() as u16).to_le_bytes());
+
+        out.write_all(b"GIF89a")?;
+        out.write_all(&width)?;
+        out.write_all(&height)?;
+        out.write_all(&[0x80 | (bits - 1) << 4 | (bits - 1), 0, 0])?;
+        for index in 0..1usize << bits {
+            let Rgb(r, g, b) = colors.get(index).copied().unwrap_or_default();
+            out.write_all(&[r, g, b])?;
+        }
+        // Loop forever.
+        out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\0\0\0")?;
+
+        let delay = self.delay.to_le_bytes();
+        let min_code_size = bits.max(2);
+        for indices in frames {
+            out.write_all(&[0x21, 0xf9, 4, 0, delay[0], delay[1], 0, 0])?;
+            out.write_all(&[0x2c, 0, 0, 0, 0])?;
+            out.write_all(&width)?;
+            out.write_all(&height)?;
+            out.write_all(&[0, min_code_size])?;
+            for block in lzw(&indices, min_code_size).chunks(255) {
+                out.write_all(&[block.len() as u8])?;
+                out.write_all(block)?;
+            }
+            out.write_all(&[0])?;
+        }
+        out.write_all(&[0x3b])
+    }
+}
+
+/// GIF flavor of LZW: codes are packed from the lowest bit and grow up to 12 bits.
+fn lzw(indices: &[usize], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODE: u16 = 4096;
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut out = Vec::new();
+    let (mut buffer, mut filled) = (0u32, 0u32);
+    let mut emit = |code: u16, width: u8| {
+        buffer |= (code as u32) << filled;
+        filled += width as u32;
+        while filled >= 8 {
+            out.push(buffer as u8);
+            buffer >>= 8;
+            filled -= 8;
+        }
+    };
+
+    let mut table = HashMap::<(u16, u16), u16>::new();
+    let mut next = end + 1;
+    let mut width = min_code_size + 1;
+    emit(clear, width);
+
+    let mut codes = indices.iter().map(|&index| index as u16);
+    if let Some(mut prefix) = codes.next() {
+        for code in codes {
+            if let Some(&known) = table.get(&(prefix, code)) {
+                prefix = known;
+                continue;
+            }
+            emit(prefix, width);
+            if next < MAX_CODE {
+                table.insert((prefix, code), next);
+                next += 1;
+                if next > 1 << width && width < 12 {
+                    width += 1;
+                }
+            } else {
+                emit(clear, width);
+                table.clear();
+                next = end + 1;
+                width = min_code_size + 1;
+            }
+            prefix = code;
+        }
+        emit(prefix, width);
+    }
+    emit(end, width);
+    if filled > 0 {
+        out.push(buffer as u8);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::matrix::{Animation, Index, Matrix, Rgb};
+    use crate::matrix::animation::lzw;
+
+    /// Reference GIF decoder for the codes written by [`lzw`].
+    fn unlzw(bytes: &[u8], min_code_size: u8) -> Vec<usize> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut bits = bytes.iter().flat_map(|&byte| (0..8).map(move |bit| (byte >> bit) & 1));
+        let mut read = |width: u8| (0..width).fold(0usize, |code, bit| code | (bits.next().unwrap() as usize) << bit);
+
+        let mut output = Vec::new();
+        let mut table = HashMap::<usize, Vec<usize>>::new();
+        let mut width = min_code_size + 1;
+        let mut previous: Option<Vec<usize>> = None;
+        loop {
+            let code = read(width);
+            if code == clear {
+                table = (0..clear).map(|i| (i, vec![i])).collect();
+                width = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return output;
+            }
+            let entry = match (table.get(&code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.as_slice(), &previous[..1]].concat(),
+                (None, None) => panic!("Unknown first code {code}"),
+            };
+            if let Some(previous) = previous {
+                let next = end + 1 + table.len() - clear;
+                if next < 4096 {
+                    table.insert(next, [previous.as_slice(), &entry[..1]].concat());
+                    if next + 1 == 1 << width && width < 12 {
+                        width += 1;
+                    }
+                }
+            }
+            output.extend_from_slice(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn check_lzw() {
+        let indices = [1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 1, 0, 0, 0, 3];
+        assert_eq!(indices.to_vec(), unlzw(&lzw(&indices, 2), 2));
+
+        let mut state = 7usize;
+        let noise = (0..20_000)
+            .map(|i| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                if i % 3 == 0 { (state >> 33) % 16 } else { i % 5 }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(noise, unlzw(&lzw(&noise, 4), 4));
+        assert_eq!(Vec::<usize>::new(), unlzw(&lzw(&[], 2), 2));
+    }
+
+    #[test]
+    fn check_gif() {
+        let palette = |cell: &u8| Rgb::new(*cell * 50, 0, 0);
+        let mut animation = Animation::new(2).delay(5);
+        let mut matrix = Matrix::from(vec![vec![0u8, 1], vec![2, 3]]);
+        animation.record(&matrix, &palette);
+        matrix.set(Index::new(0, 0), 4);
+        animation.record(&matrix, &palette);
+        assert_eq!(2, animation.len());
+
+        let mut out = Vec::new();
+        animation.write_gif(&mut out).unwrap();
+        assert_eq!(b"GIF89a\x04\0\x04\0\xa2\0\0".as_slice(), &out[..13]);
+        assert_eq!([0, 0, 0, 50, 0, 0], out[13..19]);
+        assert_eq!(2, out.windows(3).filter(|w| *w == [0x21, 0xf9, 4]).count());
+        assert_eq!(Some(&0x3b), out.last());
+    }
+
+    #[test]
+    fn check_too_many_colors() {
+        let mut animation = Animation::new(1);
+        let matrix = Matrix::from(vec![(0..300).collect::<Vec<usize>>()]);
+        animation.record(&matrix, &|cell: &usize| Rgb::new(*cell as u8, (*cell >> 8) as u8, 0));
+        let error = animation.write_gif(Vec::new()).unwrap_err();
+        assert_eq!("300 colors do not fit a GIF palette", error.to_string());
+    }
+}
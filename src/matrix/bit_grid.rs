@@ -0,0 +1,209 @@
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign};
+
+use crate::matrix::{Index, Matrix, Size};
+
+const BITS: usize = u64::BITS as usize;
+
+/// Boolean grid packed one bit per cell, every row starts at a new `u64` word.
+///
+/// Bits past the width are always zero, so `Eq` and `Hash` only look at the cells.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    size: Size,
+    row_words: usize,
+}
+
+impl BitGrid {
+    /// Grid of `size` with every cell unset.
+    pub fn new(size: Size) -> Self {
+        let row_words = size.width().div_ceil(BITS);
+        Self { words: vec![0; row_words * size.height()], size, row_words }
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    pub fn get(&self, index: Index) -> bool {
+        let (word, bit) = self.position(index);
+        self.words[word] & bit != 0
+    }
+
+    pub fn get_at(&self, x: usize, y: usize) -> bool {
+        self.get(Index(x, y))
+    }
+
+    pub fn set(&mut self, index: Index, value: bool) {
+        let (word, bit) = self.position(index);
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+    }
+
+    pub fn set_at(&mut self, x: usize, y: usize, value: bool) {
+        self.set(Index(x, y), value)
+    }
+
+    pub fn toggle(&mut self, index: Index) {
+        let (word, bit) = self.position(index);
+        self.words[word] ^= bit;
+    }
+
+    /// Number of set cells.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn row_count_ones(&self, y: usize) -> usize {
+        self.row(y).iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Packed words of row `y`, cell `x` is bit `x % 64` of word `x / 64`.
+    pub fn row(&self, y: usize) -> &[u64] {
+        assert!(y < self.size.height(), "Row {y} is out of bounds of {} grid", self.size);
+        &self.words[y * self.row_words..(y + 1) * self.row_words]
+    }
+
+    /// Set cells row by row.
+    pub fn ones(&self) -> impl Iterator<Item=Index> + '_ {
+        (0..self.size.height()).flat_map(move |y| {
+            self.row(y).iter().enumerate().flat_map(move |(i, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(Index(i * BITS + bit, y))
+                })
+            })
+        })
+    }
+
+    /// Moves every row `rows` up, rows appearing at the bottom are empty.
+    pub fn shift_up(&mut self, rows: usize) {
+        let rows = rows.min(self.size.height());
+        let len = self.words.len();
+        self.words.copy_within(rows * self.row_words.., 0);
+        self.words[len - rows * self.row_words..].fill(0);
+    }
+
+    /// Moves every row `rows` down, rows appearing at the top are empty.
+    pub fn shift_down(&mut self, rows: usize) {
+        let rows = rows.min(self.size.height());
+        let len = self.words.len();
+        self.words.copy_within(..len - rows * self.row_words, rows * self.row_words);
+        self.words[..rows * self.row_words].fill(0);
+    }
+
+    fn position(&self, index: Index) -> (usize, u64) {
+        assert!(self.size.contains(index), "Index {index} is out of bounds of {} grid", self.size);
+        (index.y() * self.row_words + index.x() / BITS, 1 << (index.x() % BITS))
+    }
+
+    fn assert_same_size(&self, other: &BitGrid) {
+        assert_eq!(self.size, other.size, "Grid size mismatch");
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        self.words.iter_mut().zip(&other.words).for_each(|(word, other)| *word &= other);
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        self.words.iter_mut().zip(&other.words).for_each(|(word, other)| *word |= other);
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        self.words.iter_mut().zip(&other.words).for_each(|(word, other)| *word ^= other);
+    }
+}
+
+impl From<&Matrix<bool>> for BitGrid {
+    fn from(matrix: &Matrix<bool>) -> Self {
+        let mut grid = BitGrid::new(matrix.size());
+        for index in matrix.positions(|cell| *cell) {
+            grid.set(index, true);
+        }
+        grid
+    }
+}
+
+impl From<&BitGrid> for Matrix<bool> {
+    fn from(grid: &BitGrid) -> Self {
+        let mut matrix = Matrix::filled(grid.size, false);
+        for index in grid.ones() {
+            matrix.set(index, true);
+        }
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::matrix::{BitGrid, Index, Matrix, Size};
+
+    #[test]
+    fn check_conversion() {
+        let mut matrix = Matrix::filled(Size::new(70, 3), false);
+        for (x, y) in [(0, 0), (63, 0), (64, 1), (69, 2), (5, 2)] {
+            matrix.set_at(x, y, true);
+        }
+        let grid = BitGrid::from(&matrix);
+        assert_eq!(5, grid.count_ones());
+        assert_eq!(2, grid.row_count_ones(2));
+        assert!(grid.get_at(63, 0) && grid.get_at(64, 1) && !grid.get_at(64, 0));
+        assert_eq!(vec![Index::new(0, 0), Index::new(63, 0), Index::new(64, 1), Index::new(5, 2), Index::new(69, 2)], grid.ones().collect::<Vec<_>>());
+        assert_eq!(matrix, Matrix::from(&grid));
+    }
+
+    #[test]
+    fn check_shifts() {
+        let mut grid = BitGrid::new(Size::new(70, 3));
+        for (x, y) in [(0, 0), (63, 0), (64, 1), (69, 2), (5, 2)] {
+            grid.set_at(x, y, true);
+        }
+        grid.shift_up(1);
+        assert!(grid.get_at(64, 0) && grid.get_at(69, 1));
+        assert_eq!(0, grid.row_count_ones(2));
+        grid.shift_down(2);
+        assert_eq!(vec![Index::new(64, 2)], grid.ones().collect::<Vec<_>>());
+        grid.shift_down(5);
+        assert_eq!(0, grid.count_ones());
+    }
+
+    #[test]
+    fn check_ops_and_hash() {
+        let mut first = BitGrid::new(Size::new(70, 3));
+        for (x, y) in [(0, 0), (63, 0), (64, 1), (69, 2), (5, 2)] {
+            first.set_at(x, y, true);
+        }
+        let mut second = BitGrid::new(first.size());
+        second.set_at(5, 2, true);
+        second.toggle(Index::new(6, 2));
+        let mut both = first.clone();
+        both &= &second;
+        assert_eq!(vec![Index::new(5, 2)], both.ones().collect::<Vec<_>>());
+        let mut any = first.clone();
+        any |= &second;
+        assert_eq!(6, any.count_ones());
+        any ^= &second;
+        any.set_at(5, 2, true);
+        assert_eq!(first, any);
+        assert_eq!(2, HashSet::from([first, any, second]).len());
+    }
+}
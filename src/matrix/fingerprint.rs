@@ -0,0 +1,89 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::matrix::{Index, Matrix, Size};
+
+/// 128-bit content hash of a [`Matrix`].
+///
+/// Every cell contributes a mix of its position and its own hash, the contributions are summed,
+/// so a single cell change is applied with [`Fingerprint::update`] without rehashing the grid.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Fingerprint {
+    value: u128,
+    size: Size,
+}
+
+impl Fingerprint {
+    /// Hash value, the matrix size is kept separately.
+    pub fn value(&self) -> u128 {
+        self.value
+    }
+
+    /// Replaces the contribution of `old` at `index` with the one of `new`.
+    pub fn update<T: Hash>(&mut self, index: Index, old: &T, new: &T) {
+        assert!(self.size.contains(index), "Index {index} is out of bounds of {} matrix", self.size);
+        let position = index.y() * self.size.width() + index.x();
+        self.value = self.value
+            .wrapping_sub(contribution(position, old))
+            .wrapping_add(contribution(position, new));
+    }
+}
+
+impl<T> Matrix<T> where T: Hash {
+    pub fn fingerprint(&self) -> Fingerprint {
+        let value = self.inner.iter()
+            .enumerate()
+            .fold(0u128, |value, (position, cell)| value.wrapping_add(contribution(position, cell)));
+        Fingerprint { value, size: self.size }
+    }
+
+    /// Sets the cell and keeps `fingerprint` of this matrix up to date.
+    pub fn set_tracked(&mut self, index: Index, value: T, fingerprint: &mut Fingerprint) {
+        fingerprint.update(index, self.get(index), &value);
+        self.set(index, value)
+    }
+}
+
+fn contribution<T: Hash>(position: usize, cell: &T) -> u128 {
+    let mut hasher = DefaultHasher::new();
+    cell.hash(&mut hasher);
+    let cell = hasher.finish();
+    let position = position as u64;
+    let high = mix(cell ^ mix(position.wrapping_add(0x9e37_79b9_7f4a_7c15)));
+    let low = mix(cell.rotate_left(32) ^ mix(position ^ 0xc2b2_ae3d_27d4_eb4f));
+    (high as u128) << 64 | low as u128
+}
+
+/// Finalizer of SplitMix64, every input bit affects every output bit.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::{Index, Matrix};
+
+    #[test]
+    fn check_fingerprint() {
+        let matrix = Matrix::from(vec![vec![Some('O'), None, Some('#')], vec![None, Some('O'), None]]);
+        assert_eq!(matrix.fingerprint(), matrix.clone().fingerprint());
+        let mut moved = matrix.clone();
+        moved.set_at(0, 0, None);
+        moved.set_at(0, 1, Some('O'));
+        assert_ne!(matrix.fingerprint(), moved.fingerprint());
+        assert_ne!(matrix.fingerprint(), matrix.transposed().fingerprint());
+    }
+
+    #[test]
+    fn check_update() {
+        let mut matrix = Matrix::from(vec![vec![Some('O'), None, Some('#')], vec![None, Some('O'), None]]);
+        let mut fingerprint = matrix.fingerprint();
+        matrix.set_tracked(Index::new(0, 0), None, &mut fingerprint);
+        matrix.set_tracked(Index::new(2, 1), Some('O'), &mut fingerprint);
+        assert_eq!(matrix.fingerprint(), fingerprint);
+        matrix.set_tracked(Index::new(2, 1), None, &mut fingerprint);
+        matrix.set_tracked(Index::new(0, 0), Some('O'), &mut fingerprint);
+        assert_eq!(Matrix::from(vec![vec![Some('O'), None, Some('#')], vec![None, Some('O'), None]]).fingerprint(), fingerprint);
+    }
+}
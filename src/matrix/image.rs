@@ -0,0 +1,198 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::matrix::{Matrix, Size};
+
+/// Cell side in pixels used by [`Matrix::save_image`].
+pub const DEFAULT_SCALE: usize = 8;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self(r, g, b)
+    }
+}
+
+/// Color of a single cell when a [`Matrix`] is drawn as an image.
+///
+/// Implemented for closures `Fn(&T) -> Rgb`.
+pub trait Palette<T> {
+    fn color(&self, cell: &T) -> Rgb;
+}
+
+impl<T, F> Palette<T> for F where F: Fn(&T) -> Rgb {
+    fn color(&self, cell: &T) -> Rgb {
+        self(cell)
+    }
+}
+
+/// RGB picture, written as binary PPM or as PNG.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Image {
+    pixels: Vec<Rgb>,
+    size: Size,
+}
+
+impl Image {
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        assert!(x < self.size.width() && y < self.size.height(), "Pixel ({x}, {y}) is out of bounds of {} image", self.size);
+        self.pixels[y * self.size.width() + x]
+    }
+
+    pub(crate) fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    /// Writes a PPM when `path` ends with `.ppm` and a PNG otherwise.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let mut out = BufWriter::new(File::create(path)?);
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("ppm")) {
+            self.write_ppm(&mut out)?;
+        } else {
+            self.write_png(&mut out)?;
+        }
+        out.flush()
+    }
+
+    pub fn write_ppm(&self, mut out: impl Write) -> std::io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.size.width(), self.size.height())?;
+        let bytes = self.pixels.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]).collect::<Vec<_>>();
+        out.write_all(&bytes)
+    }
+
+    /// 8-bit RGB PNG, the image data is stored without compression.
+    pub fn write_png(&self, mut out: impl Write) -> std::io::Result<()> {
+        let (width, height) = (self.size.width(), self.size.height());
+        let mut header = Vec::with_capacity(13);
+        header.extend((width as u32).to_be_bytes());
+        header.extend((height as u32).to_be_bytes());
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut raw = Vec::with_capacity((width * 3 + 1) * height);
+        for row in self.pixels.chunks(width.max(1)) {
+            raw.push(0);
+            raw.extend(row.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        }
+
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+        write_chunk(&mut out, b"IHDR", &header)?;
+        write_chunk(&mut out, b"IDAT", &zlib_stored(&raw))?;
+        write_chunk(&mut out, b"IEND", &[])
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Draws every cell as a `scale`×`scale` block of its palette color.
+    pub fn to_image(&self, palette: &impl Palette<T>, scale: usize) -> Image {
+        assert!(scale > 0, "Scale must be positive");
+        let size = Size(self.size.width() * scale, self.size.height() * scale);
+        let mut pixels = Vec::with_capacity(size.area());
+        for row in self.inner.chunks(self.size.width().max(1)) {
+            let colors = row.iter().map(|cell| palette.color(cell)).collect::<Vec<_>>();
+            let line = colors.iter().flat_map(|&color| std::iter::repeat_n(color, scale)).collect::<Vec<_>>();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        Image { pixels, size }
+    }
+
+    /// Saves the matrix with [`DEFAULT_SCALE`] pixels per cell, see [`Image::save`] for the format.
+    pub fn save_image(&self, path: impl AsRef<Path>, palette: impl Palette<T>) -> std::io::Result<()> {
+        self.to_image(&palette, DEFAULT_SCALE).save(path)
+    }
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> std::io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data));
+    out.write_all(&crc.to_be_bytes())
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item=&'a u8>) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const BLOCK: usize = u16::MAX as usize;
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(blocks.peek().is_none() as u8);
+        out.extend((block.len() as u16).to_le_bytes());
+        out.extend((!(block.len() as u16)).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend(((b << 16) | a).to_be_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::{Matrix, Rgb, Size};
+    use crate::matrix::image::{crc32, zlib_stored};
+
+    fn palette(cell: &bool) -> Rgb {
+        if *cell { Rgb::WHITE } else { Rgb::new(10, 20, 30) }
+    }
+
+    #[test]
+    fn check_to_image() {
+        let matrix = Matrix::from(vec![vec![true, false], vec![false, false]]);
+        let image = matrix.to_image(&palette, 3);
+        assert_eq!(Size::new(6, 6), image.size());
+        assert_eq!(Rgb::WHITE, image.get(2, 2));
+        assert_eq!(Rgb::new(10, 20, 30), image.get(3, 0));
+        assert_eq!(Rgb::new(10, 20, 30), image.get(0, 3));
+    }
+
+    #[test]
+    fn check_ppm() {
+        let matrix = Matrix::from(vec![vec![true, false]]);
+        let mut out = Vec::new();
+        matrix.to_image(&palette, 1).write_ppm(&mut out).unwrap();
+        assert_eq!(b"P6\n2 1\n255\n\xff\xff\xff\x0a\x14\x1e".as_slice(), out);
+    }
+
+    #[test]
+    fn check_png() {
+        assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+        assert_eq!(vec![0x78, 0x01, 1, 3, 0, 0xfc, 0xff, b'a', b'b', b'c', 0x02, 0x4d, 0x01, 0x27], zlib_stored(b"abc"));
+
+        let matrix = Matrix::from(vec![vec![true, false]]);
+        let mut out = Vec::new();
+        matrix.to_image(&palette, 1).write_png(&mut out).unwrap();
+        assert_eq!(b"\x89PNG\r\n\x1a\n".as_slice(), &out[..8]);
+        assert_eq!(b"\0\0\0\x0dIHDR\0\0\0\x02\0\0\0\x01\x08\x02\0\0\0".as_slice(), &out[8..29]);
+        assert_eq!(b"\0\0\0\0IEND\xae\x42\x60\x82".as_slice(), &out[out.len() - 12..]);
+    }
+}
@@ -0,0 +1,122 @@
+use std::slice;
+
+use rayon::prelude::*;
+
+use crate::matrix::{Index, Matrix};
+
+impl<T> Matrix<T> {
+    /// Cells row by row.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.inner.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.inner.iter_mut()
+    }
+
+    /// All indexes row by row, in the order of [`Matrix::iter`].
+    pub fn indexes(&self) -> impl Iterator<Item=Index> {
+        let width = self.size.width();
+        (0..self.inner.len()).map(move |i| Index(i % width, i / width))
+    }
+
+    pub fn indexed_iter(&self) -> impl Iterator<Item=(Index, &T)> {
+        self.indexes().zip(self.inner.iter())
+    }
+
+    pub fn indexed_iter_mut(&mut self) -> impl Iterator<Item=(Index, &mut T)> {
+        self.indexes().zip(self.inner.iter_mut())
+    }
+
+    /// Matrix of the same size with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Matrix<U> {
+        Matrix { inner: self.inner.iter().map(f).collect(), size: self.size }
+    }
+
+    /// Indexes of the cells matching `predicate`, row by row.
+    pub fn positions<'a>(&'a self, mut predicate: impl FnMut(&T) -> bool + 'a) -> impl Iterator<Item=Index> + 'a {
+        self.indexed_iter()
+            .filter_map(move |(index, cell)| predicate(cell).then_some(index))
+    }
+
+    /// First index matching `predicate`, row by row.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Index> {
+        self.positions(predicate).next()
+    }
+
+    pub fn count(&self, mut predicate: impl FnMut(&T) -> bool) -> usize {
+        self.inner.iter().filter(|cell| predicate(cell)).count()
+    }
+}
+
+impl<T> Matrix<T> where T: Sync {
+    /// Rows as slices, processed in parallel.
+    pub fn par_rows(&self) -> rayon::slice::Chunks<'_, T> {
+        self.inner.par_chunks(self.size.width().max(1))
+    }
+
+    pub fn par_indexed_iter(&self) -> impl IndexedParallelIterator<Item=(Index, &T)> {
+        let width = self.size.width();
+        self.inner.par_iter()
+            .enumerate()
+            .map(move |(i, cell)| (Index(i % width, i / width), cell))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Matrix<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Matrix<T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use crate::matrix::{Index, Matrix};
+
+    #[test]
+    fn check_iter() {
+        let mut matrix = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(21, matrix.iter().sum::<u32>());
+        matrix.iter_mut().for_each(|cell| *cell *= 2);
+        let indexed = matrix.indexed_iter().map(|(index, cell)| (index.into(), *cell)).collect::<Vec<_>>();
+        assert_eq!(vec![((0, 0), 2), ((1, 0), 4), ((2, 0), 6), ((0, 1), 8), ((1, 1), 10), ((2, 1), 12)], indexed);
+        for (index, cell) in matrix.indexed_iter_mut() {
+            *cell = index.x() as u32;
+        }
+        assert_eq!(vec![0, 1, 2, 0, 1, 2], (&matrix).into_iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn check_search() {
+        let matrix = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let odd = matrix.map(|cell| cell % 2 == 1);
+        assert_eq!(matrix.size(), odd.size());
+        assert_eq!(vec![Index::new(0, 0), Index::new(2, 0), Index::new(1, 1)], odd.positions(|odd| *odd).collect::<Vec<_>>());
+        assert_eq!(Some(Index::new(0, 1)), matrix.find(|cell| *cell > 3));
+        assert_eq!(None, matrix.find(|cell| *cell > 6));
+        assert_eq!(3, matrix.count(|cell| cell % 2 == 0));
+    }
+
+    #[test]
+    fn check_parallel() {
+        let matrix = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let sums = matrix.par_rows().map(|row| row.iter().sum::<u32>()).collect::<Vec<_>>();
+        assert_eq!(vec![6, 15], sums);
+        let weighted = matrix.par_indexed_iter().map(|(index, cell)| index.y() as u32 * cell).sum::<u32>();
+        assert_eq!(15, weighted);
+    }
+}
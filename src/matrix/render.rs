@@ -0,0 +1,333 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use colored::{Color, Colorize};
+
+use crate::cell::ToChar;
+use crate::matrix::{Index, Matrix};
+
+/// Text of a single cell when a [`Matrix`] is rendered.
+///
+/// Implemented for closures `Fn(&T) -> String`, [`Displayed`] and [`Chars`].
+pub trait CellRenderer<T> {
+    fn render(&self, cell: &T) -> String;
+}
+
+impl<T, F> CellRenderer<T> for F where F: Fn(&T) -> String {
+    fn render(&self, cell: &T) -> String {
+        self(cell)
+    }
+}
+
+/// Renders cells with their `Display`.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Displayed;
+
+impl<T> CellRenderer<T> for Displayed where T: Display {
+    fn render(&self, cell: &T) -> String {
+        cell.to_string()
+    }
+}
+
+/// Renders cells with their [`ToChar`], the puzzle input layout.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Chars;
+
+impl<T> CellRenderer<T> for Chars where T: ToChar {
+    fn render(&self, cell: &T) -> String {
+        cell.to_char().to_string()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// Named set of cells highlighted with a color when a matrix is rendered.
+#[derive(Debug, Clone)]
+pub struct Layer {
+    name: String,
+    indexes: HashSet<Index>,
+    style: Highlight,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+struct Highlight {
+    color: Option<Color>,
+    background: Option<Color>,
+    bold: bool,
+}
+
+impl Highlight {
+    /// `other` drawn on top of `self`.
+    fn with(self, other: Highlight) -> Self {
+        Self {
+            color: other.color.or(self.color),
+            background: other.background.or(self.background),
+            bold: self.bold || other.bold,
+        }
+    }
+
+    fn paint(&self, text: &str) -> String {
+        let mut text = text.normal();
+        if let Some(color) = self.color {
+            text = text.color(color);
+        }
+        if let Some(background) = self.background {
+            text = text.on_color(background);
+        }
+        if self.bold {
+            text = text.bold();
+        }
+        text.to_string()
+    }
+}
+
+impl Layer {
+    pub fn new(name: impl Into<String>, indexes: impl IntoIterator<Item=Index>) -> Self {
+        Self { name: name.into(), indexes: indexes.into_iter().collect(), style: Highlight::default() }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn contains(&self, index: Index) -> bool {
+        self.indexes.contains(&index)
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.color = Some(color);
+        self
+    }
+
+    pub fn on(mut self, background: Color) -> Self {
+        self.style.background = Some(background);
+        self
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.style.bold = true;
+        self
+    }
+}
+
+/// Matrix printed through a [`CellRenderer`], created by [`Matrix::render`].
+///
+/// Without options every row is printed as is and followed by a new line,
+/// which reproduces the puzzle text.
+///
+/// Highlight [`Layer`]s are drawn in the order they were added, later ones on top.
+/// Like all `colored` output they fall back to plain text when stdout is not a TTY.
+pub struct Rendered<'a, T, R> {
+    matrix: &'a Matrix<T>,
+    renderer: &'a R,
+    separator: String,
+    width: usize,
+    auto_width: bool,
+    align: Align,
+    ruler: bool,
+    layers: Vec<Layer>,
+    legend: bool,
+}
+
+impl<T> Matrix<T> {
+    pub fn render<'a, R: CellRenderer<T>>(&'a self, renderer: &'a R) -> Rendered<'a, T, R> {
+        Rendered {
+            matrix: self,
+            renderer,
+            separator: String::new(),
+            width: 0,
+            auto_width: false,
+            align: Align::Left,
+            ruler: false,
+            layers: Vec::new(),
+            legend: false,
+        }
+    }
+}
+
+impl<'a, T, R> Rendered<'a, T, R> where R: CellRenderer<T> {
+    /// Printed between cells of a row.
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Minimal width of every cell in chars.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Pads every cell to the widest one.
+    pub fn auto_width(mut self) -> Self {
+        self.auto_width = true;
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Adds `x` coordinates above the grid and `y` coordinates before every row.
+    pub fn ruler(mut self) -> Self {
+        self.ruler = true;
+        self
+    }
+
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Lists layer names in their colors below the grid.
+    pub fn legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
+
+    fn highlight(&self, index: Index) -> Option<Highlight> {
+        self.layers.iter()
+            .filter(|layer| layer.contains(index))
+            .map(|layer| layer.style)
+            .reduce(Highlight::with)
+    }
+
+    fn write_cell(&self, f: &mut Formatter<'_>, text: &str, width: usize) -> std::fmt::Result {
+        self.write_highlighted(f, text, width, None)
+    }
+
+    fn write_highlighted(&self, f: &mut Formatter<'_>, text: &str, width: usize, highlight: Option<Highlight>) -> std::fmt::Result {
+        let text = match self.align {
+            Align::Left => format!("{text:<width$}"),
+            Align::Right => format!("{text:>width$}"),
+            Align::Center => format!("{text:^width$}"),
+        };
+        match highlight {
+            Some(highlight) => f.write_str(&highlight.paint(&text)),
+            None => f.write_str(&text),
+        }
+    }
+
+    fn write_ruler(&self, f: &mut Formatter<'_>, width: usize, label_width: usize) -> std::fmt::Result {
+        write!(f, "{:label_width$} ", "")?;
+        let gap = " ".repeat(self.separator.chars().count());
+        for x in 0..self.matrix.size().width() {
+            if x > 0 {
+                f.write_str(&gap)?;
+            }
+            let label = if x.to_string().len() <= width.max(1) { x } else { x % 10 };
+            self.write_cell(f, &label.to_string(), width)?;
+        }
+        writeln!(f)
+    }
+}
+
+impl<T, R> Display for Rendered<'_, T, R> where R: CellRenderer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let size = self.matrix.size();
+        let cells = self.matrix.iter().map(|cell| self.renderer.render(cell)).collect::<Vec<_>>();
+        let widest = if self.auto_width {
+            cells.iter().map(|cell| cell.chars().count()).max().unwrap_or(0)
+        } else {
+            0
+        };
+        let width = self.width.max(widest);
+        let label_width = size.height().saturating_sub(1).to_string().len();
+        if self.ruler {
+            self.write_ruler(f, width, label_width)?;
+        }
+        for (y, row) in cells.chunks(size.width().max(1)).enumerate() {
+            if self.ruler {
+                write!(f, "{y:>label_width$} ")?;
+            }
+            for (x, cell) in row.iter().enumerate() {
+                if x > 0 {
+                    f.write_str(&self.separator)?;
+                }
+                self.write_highlighted(f, cell, width, self.highlight(Index(x, y)))?;
+            }
+            writeln!(f)?;
+        }
+        if self.legend && !self.layers.is_empty() {
+            let names = self.layers.iter().map(|layer| layer.style.paint(&layer.name)).collect::<Vec<_>>();
+            writeln!(f, "{}", names.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use colored::Color;
+
+    use crate::matrix::{Index, Matrix};
+    use crate::matrix::render::{Align, Chars, Displayed, Highlight, Layer};
+
+    fn grid(text: &str) -> Matrix<char> {
+        text.lines().map(|line| line.chars().collect()).collect::<Vec<Vec<_>>>().into()
+    }
+
+    /// `text` without ANSI color codes, which depend on the terminal the tests run in.
+    fn plain(text: &str) -> String {
+        let mut chars = text.chars();
+        let mut plain = String::new();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                plain.push(c);
+            }
+        }
+        plain
+    }
+
+    #[test]
+    fn check_plain() {
+        let text = "O....#....\nO.OO#....#\n.....##...\n";
+        let matrix = grid(text);
+        assert_eq!(text, matrix.render(&Chars).to_string());
+        assert_eq!(text, matrix.render(&Displayed).to_string());
+    }
+
+    #[test]
+    fn check_options() {
+        let matrix = Matrix::from(vec![vec![1, 20, 3], vec![400, 5, 6]]);
+        let rendered = matrix.render(&Displayed).separator("|").auto_width().align(Align::Right).to_string();
+        assert_eq!("  1| 20|  3\n400|  5|  6\n", rendered);
+        let centered = matrix.render(&|n: &u32| format!("{}", n % 10)).width(3).align(Align::Center).to_string();
+        assert_eq!(" 1  0  3 \n 0  5  6 \n", centered);
+    }
+
+    #[test]
+    fn check_ruler() {
+        let matrix = grid("#.#.#.#.#.#.\n.#.#.#.#.#.#\n");
+        let expected = "  012345678901\n0 #.#.#.#.#.#.\n1 .#.#.#.#.#.#\n";
+        assert_eq!(expected, matrix.render(&Chars).ruler().to_string());
+        let wide = grid("ab\ncd\n").render(&Chars).width(2).separator(" ").ruler().to_string();
+        assert_eq!("  0  1 \n0 a  b \n1 c  d \n", wide);
+    }
+
+    #[test]
+    fn check_layers() {
+        let matrix = grid("F-7\n|.|\nL-J\n");
+        let border = matrix.positions(|c| *c != '.').collect::<Vec<_>>();
+        let rendered = matrix.render(&Chars)
+            .layer(Layer::new("loop", border).color(Color::Green))
+            .layer(Layer::new("start", [Index::new(0, 0)]).on(Color::Red).bold());
+        let start = rendered.highlight(Index::new(0, 0));
+        assert_eq!(Some(Highlight { color: Some(Color::Green), background: Some(Color::Red), bold: true }), start);
+        let side = rendered.highlight(Index::new(1, 0));
+        assert_eq!(Some(Highlight { color: Some(Color::Green), background: None, bold: false }), side);
+        assert_eq!(None, rendered.highlight(Index::new(1, 1)));
+
+        assert_eq!("F-7\n|.|\nL-J\n", plain(&rendered.to_string()));
+        assert_eq!("F-7\n|.|\nL-J\nloop, start\n", plain(&rendered.legend().to_string()));
+    }
+}
@@ -0,0 +1,292 @@
+use std::mem;
+
+use crate::matrix::{Index, Matrix, Size};
+
+/// Rectangle of cells starting at `origin`, the top left corner.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    origin: Index,
+    size: Size,
+}
+
+impl Rect {
+    pub const fn new(origin: Index, size: Size) -> Self {
+        Self { origin, size }
+    }
+
+    pub fn origin(&self) -> Index {
+        self.origin
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Index right after the bottom right corner.
+    pub fn end(&self) -> Index {
+        Index(self.origin.x() + self.size.width(), self.origin.y() + self.size.height())
+    }
+
+    pub fn contains(&self, index: Index) -> bool {
+        let end = self.end();
+        (self.origin.x()..end.x()).contains(&index.x()) && (self.origin.y()..end.y()).contains(&index.y())
+    }
+
+    /// `self` fully covers `other`.
+    pub fn covers(&self, other: Rect) -> bool {
+        let (end, other_end) = (self.end(), other.end());
+        self.origin.x() <= other.origin.x() && self.origin.y() <= other.origin.y()
+            && other_end.x() <= end.x() && other_end.y() <= end.y()
+    }
+}
+
+impl From<Size> for Rect {
+    fn from(size: Size) -> Self {
+        Self::new(Index(0, 0), size)
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Rectangle of all cells.
+    pub fn bounds(&self) -> Rect {
+        Rect::from(self.size)
+    }
+
+    /// Inserts `row` before row `y`, `y == height` appends it.
+    pub fn insert_row(&mut self, y: usize, row: impl IntoIterator<Item=T>) {
+        assert!(y <= self.size.height(), "Row {y} is out of bounds of {} matrix", self.size);
+        let row = row.into_iter().collect::<Vec<_>>();
+        assert_eq!(self.size.width(), row.len(), "Row width mismatch");
+        let at = y * self.size.width();
+        self.inner.splice(at..at, row);
+        self.size.1 += 1;
+    }
+
+    /// Inserts `column` before column `x`, `x == width` appends it.
+    pub fn insert_column(&mut self, x: usize, column: impl IntoIterator<Item=T>) {
+        let Size(width, height) = self.size;
+        assert!(x <= width, "Column {x} is out of bounds of {} matrix", self.size);
+        let column = column.into_iter().collect::<Vec<_>>();
+        assert_eq!(height, column.len(), "Column height mismatch");
+        let mut cells = mem::take(&mut self.inner).into_iter();
+        let mut inner = Vec::with_capacity((width + 1) * height);
+        for value in column {
+            inner.extend(cells.by_ref().take(x));
+            inner.push(value);
+            inner.extend(cells.by_ref().take(width - x));
+        }
+        self.inner = inner;
+        self.size.0 += 1;
+    }
+
+    /// Removes row `y` and returns its cells.
+    pub fn remove_row(&mut self, y: usize) -> Vec<T> {
+        assert!(y < self.size.height(), "Row {y} is out of bounds of {} matrix", self.size);
+        let width = self.size.width();
+        self.size.1 -= 1;
+        self.inner.drain(y * width..(y + 1) * width).collect()
+    }
+
+    /// Removes column `x` and returns its cells top to bottom.
+    pub fn remove_column(&mut self, x: usize) -> Vec<T> {
+        let Size(width, height) = self.size;
+        assert!(x < width, "Column {x} is out of bounds of {} matrix", self.size);
+        let mut removed = Vec::with_capacity(height);
+        let mut inner = Vec::with_capacity((width - 1) * height);
+        for (i, cell) in mem::take(&mut self.inner).into_iter().enumerate() {
+            if i % width == x {
+                removed.push(cell);
+            } else {
+                inner.push(cell);
+            }
+        }
+        self.inner = inner;
+        self.size.0 -= 1;
+        removed
+    }
+
+    /// Surrounds the matrix with `border` rows and columns of `value` on every side.
+    pub fn pad(&mut self, border: usize, value: T) where T: Clone {
+        let Size(width, height) = self.size;
+        let size = Size(width + 2 * border, height + 2 * border);
+        let mut cells = mem::take(&mut self.inner).into_iter();
+        let mut inner = Vec::with_capacity(size.area());
+        inner.resize(border * size.width(), value.clone());
+        for _ in 0..height {
+            inner.extend(std::iter::repeat_n(value.clone(), border));
+            inner.extend(cells.by_ref().take(width));
+            inner.extend(std::iter::repeat_n(value.clone(), border));
+        }
+        inner.resize(size.area(), value);
+        self.inner = inner;
+        self.size = size;
+    }
+
+    /// Keeps only the cells inside of `rect`.
+    pub fn crop(&mut self, rect: Rect) {
+        assert!(self.bounds().covers(rect), "{rect:?} is out of bounds of {} matrix", self.size);
+        let width = self.size.width();
+        self.inner = mem::take(&mut self.inner).into_iter()
+            .enumerate()
+            .filter(|(i, _)| rect.contains(Index(i % width, i / width)))
+            .map(|(_, cell)| cell)
+            .collect();
+        self.size = rect.size();
+    }
+}
+
+/// Borrowed rectangle of a matrix, indexes are relative to its origin.
+#[derive(Debug, Copy, Clone)]
+pub struct SubMatrix<'a, T> {
+    data: &'a Matrix<T>,
+    rect: Rect,
+}
+
+impl<T> Matrix<T> {
+    pub fn sub(&self, rect: Rect) -> SubMatrix<'_, T> {
+        assert!(self.bounds().covers(rect), "{rect:?} is out of bounds of {} matrix", self.size);
+        SubMatrix { data: self, rect }
+    }
+}
+
+impl<'a, T> SubMatrix<'a, T> {
+    pub fn size(&self) -> Size {
+        self.rect.size()
+    }
+
+    /// Position in the underlying matrix.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn contains(&self, index: Index) -> bool {
+        self.size().contains(index)
+    }
+
+    pub fn get(&self, index: Index) -> &'a T {
+        assert!(self.contains(index), "Index {index} is out of bounds of {} sub matrix", self.size());
+        let origin = self.rect.origin();
+        self.data.get_at(origin.x() + index.x(), origin.y() + index.y())
+    }
+
+    pub fn get_at(&self, x: usize, y: usize) -> &'a T {
+        self.get(Index(x, y))
+    }
+
+    pub fn try_get(&self, index: Index) -> Option<&'a T> {
+        self.contains(index).then(|| self.get(index))
+    }
+
+    pub fn row_slice(&self, y: usize) -> &'a [T] {
+        assert!(y < self.size().height(), "Row {y} is out of bounds of {} sub matrix", self.size());
+        let origin = self.rect.origin();
+        let row = self.data.row_slice(origin.y() + y);
+        &row[origin.x()..origin.x() + self.size().width()]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item=&'a [T]> + ExactSizeIterator + '_ {
+        (0..self.size().height()).map(|y| self.row_slice(y))
+    }
+
+    /// Cells row by row.
+    pub fn iter(&self) -> impl Iterator<Item=&'a T> + '_ {
+        self.rows().flatten()
+    }
+
+    pub fn indexed_iter(&self) -> impl Iterator<Item=(Index, &'a T)> + '_ {
+        self.rows()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, cell)| (Index(x, y), cell)))
+    }
+
+    /// Nested sub matrix, `rect` is relative to this one.
+    pub fn sub(&self, rect: Rect) -> SubMatrix<'a, T> {
+        assert!(Rect::from(self.size()).covers(rect), "{rect:?} is out of bounds of {} sub matrix", self.size());
+        let origin = self.rect.origin();
+        let origin = Index(origin.x() + rect.origin().x(), origin.y() + rect.origin().y());
+        SubMatrix { data: self.data, rect: Rect::new(origin, rect.size()) }
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T> where T: Clone {
+        Matrix { inner: self.iter().cloned().collect(), size: self.size() }
+    }
+}
+
+impl<T> std::ops::Index<Index> for SubMatrix<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Index) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::{Index, Matrix, Size};
+    use crate::matrix::resize::Rect;
+
+    #[test]
+    fn check_rows() {
+        let original = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let mut matrix = original.clone();
+        matrix.insert_row(1, [7, 8, 9]);
+        matrix.insert_row(3, vec![0; 3]);
+        assert_eq!(Matrix::from(vec![vec![1, 2, 3], vec![7, 8, 9], vec![4, 5, 6], vec![0, 0, 0]]), matrix);
+        assert_eq!(vec![7, 8, 9], matrix.remove_row(1));
+        assert_eq!(vec![0, 0, 0], matrix.remove_row(2));
+        assert_eq!(original, matrix);
+    }
+
+    #[test]
+    fn check_columns() {
+        let original = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let mut matrix = original.clone();
+        matrix.insert_column(0, [7, 8]);
+        matrix.insert_column(4, [0, 0]);
+        assert_eq!(Matrix::from(vec![vec![7, 1, 2, 3, 0], vec![8, 4, 5, 6, 0]]), matrix);
+        assert_eq!(vec![0, 0], matrix.remove_column(4));
+        assert_eq!(vec![7, 8], matrix.remove_column(0));
+        assert_eq!(original, matrix);
+    }
+
+    #[test]
+    #[should_panic(expected = "Row width mismatch")]
+    fn check_row_width() {
+        Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]).insert_row(0, [1, 2]);
+    }
+
+    #[test]
+    fn check_row_width_keeps_matrix() {
+        let original = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let mut matrix = original.clone();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| matrix.insert_row(1, [7, 8])));
+        assert!(result.is_err());
+        assert_eq!(original, matrix);
+    }
+
+    #[test]
+    fn check_pad_crop() {
+        let original = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let mut matrix = original.clone();
+        matrix.pad(1, 0);
+        assert_eq!(Size::new(5, 4), matrix.size());
+        assert_eq!(vec![0, 4, 5, 6, 0], matrix.row_slice(2));
+        assert_eq!(14, matrix.count(|cell| *cell == 0));
+        matrix.crop(Rect::new(Index::new(1, 1), Size::new(3, 2)));
+        assert_eq!(original, matrix);
+    }
+
+    #[test]
+    fn check_sub_matrix() {
+        let matrix = Matrix::from(vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10, 11, 12]]);
+        let sub = matrix.sub(Rect::new(Index::new(1, 1), Size::new(3, 2)));
+        assert_eq!(&6, sub.get_at(0, 0));
+        assert_eq!(12, sub[Index::new(2, 1)]);
+        assert_eq!(None, sub.try_get(Index::new(3, 0)));
+        assert_eq!(vec![&[10, 11, 12][..], &[6, 7, 8][..]], sub.rows().rev().collect::<Vec<_>>());
+        assert_eq!(Some((Index::new(2, 1), &12)), sub.indexed_iter().last());
+        let nested = sub.sub(Rect::new(Index::new(1, 0), Size::new(1, 2)));
+        assert_eq!(Matrix::from(vec![vec![7], vec![11]]), nested.to_matrix());
+    }
+}
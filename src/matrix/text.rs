@@ -0,0 +1,153 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::cell::{FromChar, ToChar};
+use crate::matrix::{Index, Matrix, Size};
+
+/// Reason [`Matrix::from_text`] rejected its input.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TextError {
+    /// `ch` at `index` is not a cell of the matrix type.
+    UnknownChar { index: Index, ch: char },
+    /// Row `y` is `width` chars long while the first row is `expected`.
+    RaggedRow { y: usize, width: usize, expected: usize },
+}
+
+impl Display for TextError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextError::UnknownChar { index, ch } => write!(f, "Unknown cell `{ch}` at {index}"),
+            TextError::RaggedRow { y, width, expected } =>
+                write!(f, "Row {y} has {width} cells, expected {expected}"),
+        }
+    }
+}
+
+impl Error for TextError {}
+
+impl<T> Matrix<T> where T: ToChar {
+    /// The AoC layout: one char per cell and every row ended by `\n`.
+    pub fn to_text(&self) -> String {
+        let mut text = String::with_capacity((self.size.width() + 1) * self.size.height());
+        let width = self.size.width();
+        for y in 0..self.size.height() {
+            text.extend(self.inner[y * width..(y + 1) * width].iter().map(ToChar::to_char));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T> Matrix<T> where T: FromChar {
+    /// Reads the layout written by [`Matrix::to_text`], `\r\n` line endings and a missing
+    /// final newline are accepted.
+    ///
+    /// Empty lines after the last row are ignored. Text made of empty lines only is a matrix
+    /// of width 0 with a row per line, the way [`Matrix::to_text`] writes it.
+    pub fn from_text(text: &str) -> Result<Self, TextError> {
+        let mut lines = text.lines().collect::<Vec<_>>();
+        if let Some(last) = lines.iter().rposition(|line| !line.is_empty()) {
+            lines.truncate(last + 1);
+        }
+        let mut inner = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in lines.into_iter().enumerate() {
+            let start = inner.len();
+            for (x, ch) in line.chars().enumerate() {
+                let cell = T::from_char(ch).ok_or(TextError::UnknownChar { index: Index(x, y), ch })?;
+                inner.push(cell);
+            }
+            let row_width = inner.len() - start;
+            let expected = *width.get_or_insert(row_width);
+            if row_width != expected {
+                return Err(TextError::RaggedRow { y, width: row_width, expected });
+            }
+            height += 1;
+        }
+        Ok(Self { inner, size: Size(width.unwrap_or(0), height) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::{FromChar, ToChar};
+    use crate::matrix::{Index, Matrix, Size, TextError};
+    use crate::testing::{check_round_trip, grids};
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, FromChar, ToChar)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    enum Rock {
+        #[ch = '.']
+        Empty,
+        #[ch = '#']
+        Cube,
+        #[ch = 'O']
+        Round,
+    }
+
+    #[test]
+    fn check_text() {
+        let matrix = Matrix::from(vec![
+            vec![Rock::Round, Rock::Empty, Rock::Cube],
+            vec![Rock::Empty, Rock::Round, Rock::Empty],
+        ]);
+        assert_eq!("O.#\n.O.\n", matrix.to_text());
+        assert_eq!(Ok(matrix.clone()), Matrix::from_text("O.#\n.O.\n"));
+        assert_eq!(Ok(matrix), Matrix::from_text("O.#\r\n.O."));
+        assert_eq!(Size::new(0, 0), Matrix::<Rock>::from_text("").unwrap().size());
+    }
+
+    #[test]
+    fn check_text_empty_lines() {
+        let matrix = Matrix::<Rock>::from_text("O.#\n.O.\n\n\n").unwrap();
+        assert_eq!(Size::new(3, 2), matrix.size());
+        assert_eq!("O.#\n.O.\n", matrix.to_text());
+
+        let narrow = Matrix::<Rock>::filled(Size::new(0, 3), Rock::Empty);
+        assert_eq!("\n\n\n", narrow.to_text());
+        assert_eq!(Size::new(0, 3), Matrix::<Rock>::from_text(&narrow.to_text()).unwrap().size());
+
+        assert_eq!(
+            Err(TextError::RaggedRow { y: 1, width: 0, expected: 3 }),
+            Matrix::<Rock>::from_text("O.#\n\n.O.\n"),
+        );
+    }
+
+    #[test]
+    fn check_text_errors() {
+        assert_eq!(
+            Err(TextError::UnknownChar { index: Index::new(1, 1), ch: 'x' }),
+            Matrix::<Rock>::from_text("O.#\n.x.\n"),
+        );
+        assert_eq!(
+            Err(TextError::RaggedRow { y: 1, width: 2, expected: 3 }),
+            Matrix::<Rock>::from_text("O.#\n.O\n"),
+        );
+        assert_eq!("Unknown cell `x` at (1, 1)", TextError::UnknownChar { index: Index::new(1, 1), ch: 'x' }.to_string());
+    }
+
+    #[test]
+    fn check_text_round_trip() {
+        fn parse(input: &str) -> nom::IResult<&str, Matrix<Rock>> {
+            Ok(("", Matrix::from_text(input).expect("valid text")))
+        }
+        check_round_trip(&grids(1..12, &Rock::ALL), Matrix::to_text, parse);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn check_serde() {
+        let matrix = Matrix::<Rock>::from_text("O.#\n.O.\n").unwrap();
+        let json = serde_json::to_string(&matrix).unwrap();
+        assert_eq!(r#"{"inner":["Round","Empty","Cube","Empty","Round","Empty"],"size":[3,2]}"#, json);
+        assert_eq!(matrix, serde_json::from_str::<Matrix<Rock>>(&json).unwrap());
+
+        let index = Index::new(4, 2);
+        assert_eq!("[4,2]", serde_json::to_string(&index).unwrap());
+        assert_eq!(index, serde_json::from_str("[4,2]").unwrap());
+
+        let error = serde_json::from_str::<Matrix<Rock>>(r#"{"inner":["Round"],"size":[3,2]}"#).unwrap_err();
+        assert!(error.to_string().contains("1 cells do not fill 3x2 matrix"), "{error}");
+    }
+}
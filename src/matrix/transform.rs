@@ -0,0 +1,250 @@
+use std::mem;
+
+use crate::matrix::{Index, Matrix, Size};
+
+/// One of the 8 rotations and reflections of a grid.
+///
+/// Stored as an optional transpose followed by flips, so transforms compose without touching cells.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Transform {
+    transpose: bool,
+    flip_x: bool,
+    flip_y: bool,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform::new(false, false, false);
+    pub const TRANSPOSE: Transform = Transform::new(true, false, false);
+    pub const ROTATE_CW: Transform = Transform::new(true, true, false);
+    pub const ROTATE_CCW: Transform = Transform::new(true, false, true);
+    pub const ROTATE_180: Transform = Transform::new(false, true, true);
+    /// Mirrors left and right.
+    pub const FLIP_HORIZONTAL: Transform = Transform::new(false, true, false);
+    /// Mirrors top and bottom.
+    pub const FLIP_VERTICAL: Transform = Transform::new(false, false, true);
+
+    const fn new(transpose: bool, flip_x: bool, flip_y: bool) -> Self {
+        Self { transpose, flip_x, flip_y }
+    }
+
+    /// Applies `self` and then `next`.
+    pub fn then(self, next: Transform) -> Self {
+        let (flip_x, flip_y) = if next.transpose {
+            (self.flip_y, self.flip_x)
+        } else {
+            (self.flip_x, self.flip_y)
+        };
+        Self::new(self.transpose ^ next.transpose, flip_x ^ next.flip_x, flip_y ^ next.flip_y)
+    }
+
+    /// Size of a `size` grid after the transform.
+    pub fn size(&self, size: Size) -> Size {
+        if self.transpose {
+            Size(size.height(), size.width())
+        } else {
+            size
+        }
+    }
+
+    /// Index in the source grid of `size` for `index` of the transformed one.
+    pub fn source(&self, index: Index, size: Size) -> Index {
+        let target = self.size(size);
+        let x = if self.flip_x { target.width() - 1 - index.x() } else { index.x() };
+        let y = if self.flip_y { target.height() - 1 - index.y() } else { index.y() };
+        if self.transpose {
+            Index(y, x)
+        } else {
+            Index(x, y)
+        }
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Zero-copy view with indexes remapped by `transform`.
+    pub fn view(&self, transform: Transform) -> View<'_, T> {
+        View { data: self, transform }
+    }
+
+    /// Rearranges cells in place, swapping along permutation cycles.
+    pub fn transform(&mut self, transform: Transform) {
+        let size = transform.size(self.size);
+        let width = size.width();
+        let source = |i: usize| self.index(transform.source(Index(i % width, i / width), self.size));
+        let permutation = (0..self.inner.len()).map(source).collect::<Vec<_>>();
+        let mut visited = vec![false; permutation.len()];
+        for start in 0..permutation.len() {
+            let mut i = start;
+            while !mem::replace(&mut visited[i], true) {
+                let next = permutation[i];
+                if next == start {
+                    break;
+                }
+                self.inner.swap(i, next);
+                i = next;
+            }
+        }
+        self.size = size;
+    }
+
+    pub fn transpose(&mut self) {
+        self.transform(Transform::TRANSPOSE)
+    }
+
+    pub fn rotate_cw(&mut self) {
+        self.transform(Transform::ROTATE_CW)
+    }
+
+    pub fn rotate_ccw(&mut self) {
+        self.transform(Transform::ROTATE_CCW)
+    }
+
+    pub fn rotate_180(&mut self) {
+        self.transform(Transform::ROTATE_180)
+    }
+
+    pub fn flip_horizontal(&mut self) {
+        self.transform(Transform::FLIP_HORIZONTAL)
+    }
+
+    pub fn flip_vertical(&mut self) {
+        self.transform(Transform::FLIP_VERTICAL)
+    }
+}
+
+impl<T> Matrix<T> where T: Clone {
+    pub fn transformed(&self, transform: Transform) -> Self {
+        self.view(transform).to_matrix()
+    }
+
+    pub fn transposed(&self) -> Self {
+        self.transformed(Transform::TRANSPOSE)
+    }
+
+    pub fn rotated_cw(&self) -> Self {
+        self.transformed(Transform::ROTATE_CW)
+    }
+
+    pub fn rotated_ccw(&self) -> Self {
+        self.transformed(Transform::ROTATE_CCW)
+    }
+
+    pub fn rotated_180(&self) -> Self {
+        self.transformed(Transform::ROTATE_180)
+    }
+
+    pub fn flipped_horizontal(&self) -> Self {
+        self.transformed(Transform::FLIP_HORIZONTAL)
+    }
+
+    pub fn flipped_vertical(&self) -> Self {
+        self.transformed(Transform::FLIP_VERTICAL)
+    }
+}
+
+/// Borrowed matrix seen through a [`Transform`], cells are looked up lazily.
+#[derive(Debug, Copy, Clone)]
+pub struct View<'a, T> {
+    data: &'a Matrix<T>,
+    transform: Transform,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn size(&self) -> Size {
+        self.transform.size(self.data.size)
+    }
+
+    pub fn get(&self, index: Index) -> &'a T {
+        assert!(self.size().contains(index), "Index {index} is out of bounds of {} view", self.size());
+        self.data.get(self.transform.source(index, self.data.size))
+    }
+
+    pub fn get_at(&self, x: usize, y: usize) -> &'a T {
+        self.get(Index(x, y))
+    }
+
+    pub fn try_get(&self, index: Index) -> Option<&'a T> {
+        self.size().contains(index).then(|| self.get(index))
+    }
+
+    /// The same matrix seen through one more transform.
+    pub fn view(&self, transform: Transform) -> View<'a, T> {
+        View { data: self.data, transform: self.transform.then(transform) }
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item=&'a T> + '_ {
+        (0..self.size().width()).map(move |x| self.get_at(x, y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item=&'a T> + '_ {
+        (0..self.size().height()).map(move |y| self.get_at(x, y))
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T> where T: Clone {
+        let size = self.size();
+        let inner = (0..size.height())
+            .flat_map(|y| self.row(y))
+            .cloned()
+            .collect();
+        Matrix { inner, size }
+    }
+}
+
+impl<T> std::ops::Index<Index> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Index) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::matrix::{Index, Matrix};
+    use crate::matrix::transform::Transform;
+
+    #[rstest]
+    #[case(Transform::IDENTITY, vec![vec![1, 2, 3], vec![4, 5, 6]])]
+    #[case(Transform::TRANSPOSE, vec![vec![1, 4], vec![2, 5], vec![3, 6]])]
+    #[case(Transform::ROTATE_CW, vec![vec![4, 1], vec![5, 2], vec![6, 3]])]
+    #[case(Transform::ROTATE_CCW, vec![vec![3, 6], vec![2, 5], vec![1, 4]])]
+    #[case(Transform::ROTATE_180, vec![vec![6, 5, 4], vec![3, 2, 1]])]
+    #[case(Transform::FLIP_HORIZONTAL, vec![vec![3, 2, 1], vec![6, 5, 4]])]
+    #[case(Transform::FLIP_VERTICAL, vec![vec![4, 5, 6], vec![1, 2, 3]])]
+    fn check_transform(#[case] transform: Transform, #[case] expected: Vec<Vec<u32>>) {
+        let expected = Matrix::from(expected);
+        assert_eq!(expected, Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]).transformed(transform));
+        let mut in_place = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        in_place.transform(transform);
+        assert_eq!(expected, in_place);
+    }
+
+    #[test]
+    fn check_named_transforms() {
+        let mut matrix = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(matrix.rotated_cw().rotated_cw(), matrix.rotated_180());
+        assert_eq!(matrix.transposed().flipped_horizontal(), matrix.rotated_cw());
+        matrix.rotate_cw();
+        matrix.rotate_ccw();
+        matrix.flip_vertical();
+        matrix.flip_horizontal();
+        matrix.rotate_180();
+        matrix.transpose();
+        matrix.transpose();
+        assert_eq!(Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]), matrix);
+    }
+
+    #[test]
+    fn check_view() {
+        let matrix = Matrix::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let view = matrix.view(Transform::ROTATE_CW);
+        assert_eq!((2, 3), view.size().into());
+        assert_eq!(4, view[Index::new(0, 0)]);
+        assert_eq!(vec![&6, &3], view.row(2).collect::<Vec<_>>());
+        assert_eq!(vec![&1, &2, &3], view.column(1).collect::<Vec<_>>());
+        assert_eq!(None, view.try_get(Index::new(2, 0)));
+        let back = view.view(Transform::ROTATE_CCW);
+        assert_eq!(matrix, back.to_matrix());
+    }
+}
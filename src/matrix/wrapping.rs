@@ -0,0 +1,137 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::grid::{Grid, GridMut, Point};
+use crate::matrix::{Direction, Direction8, Index, Matrix, Size};
+
+/// Matrix tiled infinitely in every direction, signed coordinates are taken modulo its size.
+///
+/// Created by [`Matrix::wrapping`] and [`Matrix::wrapping_mut`], nothing is copied.
+#[derive(Debug, Copy, Clone)]
+pub struct Wrapping<M> {
+    matrix: M,
+}
+
+impl<T> Matrix<T> {
+    pub fn wrapping(&self) -> Wrapping<&Matrix<T>> {
+        Wrapping { matrix: self }
+    }
+
+    pub fn wrapping_mut(&mut self) -> Wrapping<&mut Matrix<T>> {
+        Wrapping { matrix: self }
+    }
+}
+
+impl<M, T> Wrapping<M> where M: Deref<Target=Matrix<T>> {
+    /// Size of one tile.
+    pub fn size(&self) -> Size {
+        self.matrix.size()
+    }
+
+    /// Index of `point` inside the matrix.
+    pub fn wrap(&self, point: Point) -> Index {
+        let size = self.size();
+        assert!(size.area() > 0, "Empty matrix can't be tiled");
+        Index(point.x().rem_euclid(size.width() as i64) as usize, point.y().rem_euclid(size.height() as i64) as usize)
+    }
+
+    /// Which copy of the matrix `point` belongs to, `(0, 0)` is the original one.
+    pub fn tile(&self, point: Point) -> Point {
+        let size = self.size();
+        assert!(size.area() > 0, "Empty matrix can't be tiled");
+        Point::new(point.x().div_euclid(size.width() as i64), point.y().div_euclid(size.height() as i64))
+    }
+
+    pub fn get(&self, point: Point) -> &T {
+        self.matrix.get(self.wrap(point))
+    }
+
+    /// Side neighbors, coordinates keep growing past the edges instead of wrapping.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item=Point> {
+        Direction::ALL.into_iter().map(move |direction| point.step(direction))
+    }
+
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item=Point> {
+        Direction8::ALL.into_iter().map(move |direction| point.step(direction))
+    }
+}
+
+impl<M, T> Wrapping<M> where M: DerefMut<Target=Matrix<T>> {
+    pub fn get_mut(&mut self, point: Point) -> &mut T {
+        let index = self.wrap(point);
+        &mut self.matrix[index]
+    }
+
+    /// Sets the cell in every tile at once.
+    pub fn set(&mut self, point: Point, value: T) {
+        *self.get_mut(point) = value
+    }
+}
+
+impl<M, T> Grid for Wrapping<M> where M: Deref<Target=Matrix<T>> {
+    type Cell = T;
+    type Index = Point;
+    /// Size of one tile, the grid itself is unbounded.
+    type Bounds = Size;
+
+    fn get(&self, index: Point) -> Option<&T> {
+        Some(Wrapping::get(self, index))
+    }
+
+    fn neighbors(&self, index: Point) -> impl Iterator<Item=Point> {
+        self.neighbors4(index)
+    }
+
+    fn bounds(&self) -> Size {
+        self.size()
+    }
+}
+
+impl<M, T> GridMut for Wrapping<M> where M: DerefMut<Target=Matrix<T>> {
+    fn set(&mut self, index: Point, value: T) {
+        Wrapping::set(self, index, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{distances, Point};
+    use crate::matrix::{Index, Matrix, Size};
+
+    #[test]
+    fn check_wrap() {
+        let garden = Matrix::from(vec![vec!['.', '.', '.'], vec!['.', '#', '.'], vec!['.', '.', '.']]);
+        let wrapping = garden.wrapping();
+        assert_eq!(Index::new(1, 1), wrapping.wrap(Point::new(-2, 4)));
+        assert_eq!(Point::new(-1, 1), wrapping.tile(Point::new(-2, 4)));
+        assert_eq!(&'#', wrapping.get(Point::new(7, -5)));
+        assert!(wrapping.neighbors4(Point::new(0, 0)).any(|point| point == Point::new(-1, 0)));
+        assert_eq!(8, wrapping.neighbors8(Point::new(0, 0)).count());
+    }
+
+    #[test]
+    fn check_set() {
+        let mut garden = Matrix::from(vec![vec!['.', '.', '.'], vec!['.', '#', '.'], vec!['.', '.', '.']]);
+        garden.wrapping_mut().set(Point::new(-3, -3), 'S');
+        assert_eq!(&'S', garden.get_at(0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Empty matrix can't be tiled")]
+    fn check_empty_tile() {
+        let empty = Matrix::<char>::filled(Size::new(0, 3), '.');
+        empty.wrapping().tile(Point::new(1, 1));
+    }
+
+    #[test]
+    fn check_search() {
+        let garden = Matrix::from(vec![vec!['.', '.', '.'], vec!['.', '#', '.'], vec!['.', '.', '.']]);
+        let wrapping = garden.wrapping();
+        let steps = distances(&wrapping, Point::new(0, 0), |point, cell| {
+            cell != Some(&'#') && point.x().abs() <= 4 && point.y().abs() <= 4
+        });
+        assert_eq!(Some(&3), steps.get(&Point::new(-3, 0)));
+        assert!(!steps.contains_key(&Point::new(-2, -2)));
+        assert!(!steps.contains_key(&Point::new(4, 1)));
+        assert_eq!(Some(&6), steps.get(&Point::new(4, 2)));
+    }
+}
@@ -0,0 +1,67 @@
+use std::str::FromStr;
+
+use nom::error::{ErrorKind, make_error};
+use nom::IResult;
+
+pub use advent_of_code_2023_macros::scan;
+
+/// Matches a literal piece of a [`scan!`] template, errors point at the first mismatched char.
+pub fn literal<'i>(input: &'i str, literal: &str) -> IResult<&'i str, ()> {
+    match input.strip_prefix(literal) {
+        Some(rest) => Ok((rest, ())),
+        None => {
+            let matched = input.char_indices()
+                .zip(literal.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(input.len(), |((i, _), _)| i);
+            Err(nom::Err::Error(make_error(&input[matched..], ErrorKind::Tag)))
+        }
+    }
+}
+
+/// Parses a [`scan!`] capture which ends before `next` literal on the same line, or at the line end.
+///
+/// If `next` is not on the line the capture ends before its first char, so the literal reports the mismatch.
+pub fn capture<'i, T: FromStr>(input: &'i str, next: Option<&str>) -> IResult<&'i str, T> {
+    let line = &input[..input.find(['\r', '\n']).unwrap_or(input.len())];
+    let end = match next {
+        Some(next) => line.find(next)
+            .or_else(|| next.chars().next().and_then(|c| line.find(c)))
+            .unwrap_or(line.len()),
+        None => line.len(),
+    };
+    let output = input[..end].parse()
+        .map_err(|_| nom::Err::Error(make_error(input, ErrorKind::MapRes)))?;
+    Ok((&input[end..], output))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scanf::scan;
+
+    #[test]
+    fn check_scan() {
+        let parser = scan!("{} = ({}, {})", String, String, String);
+        let (remain, (key, left, right)) = parser("AAA = (BBB, CCC)\nBBB").expect("line parsed");
+        assert_eq!("\nBBB", remain);
+        assert_eq!(("AAA", "BBB", "CCC"), (key.as_str(), left.as_str(), right.as_str()));
+
+        let (remain, (hand, bid)) = scan!("{} {}", String, u32)("32T3K 765").expect("line parsed");
+        assert!(remain.is_empty());
+        assert_eq!(("32T3K", 765), (hand.as_str(), bid));
+
+        let (_, (x, y)) = scan!("{{{},{}}}", i32, i32)("{-1,2}").expect("braces parsed");
+        assert_eq!((-1, 2), (x, y));
+    }
+
+    #[test]
+    fn check_scan_errors() {
+        let parser = scan!("Game {}: {}", u32, String);
+        let err = parser("Gaem 1: x").expect_err("literal mismatch");
+        assert!(matches!(err, nom::Err::Error(e) if e.input == "em 1: x"));
+        let err = parser("Game x1: x").expect_err("capture is not a number");
+        assert!(matches!(err, nom::Err::Error(e) if e.input == "x1: x"));
+        let err = parser("Game 1 x\nGame 2: y").expect_err("literal is not on the line");
+        assert!(matches!(err, nom::Err::Error(e) if e.input == "1 x\nGame 2: y"));
+    }
+}
@@ -0,0 +1,122 @@
+use nom::{IResult, Offset, Slice};
+use nom_locate::LocatedSpan;
+
+/// Input which keeps line and column of every parsed token.
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+/// Parsed value with the position of its text in the input.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Spanned<T> {
+    value: T,
+    line: u32,
+    column: usize,
+    len: usize,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(value: T, line: u32, column: usize, len: usize) -> Self {
+        Self { value, line, column, len }
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// 1-based line number.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// 1-based column in chars.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// 0-based column, the grid `x` coordinate.
+    pub fn x(&self) -> usize {
+        self.column - 1
+    }
+
+    /// 0-based line, the grid `y` coordinate.
+    pub fn y(&self) -> usize {
+        self.line as usize - 1
+    }
+
+    /// Length of the parsed text in chars.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Spanned<U> {
+        Spanned::new(f(self.value), self.line, self.column, self.len)
+    }
+}
+
+/// Runs a `&str` parser on located input, so every `&str` parser of the crate works with [`Span`].
+///
+/// Errors keep pointing at the exact token.
+pub fn located<'a, O, F>(mut parser: F) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, O>
+    where F: FnMut(&'a str) -> IResult<&'a str, O> {
+    move |input: Span<'a>| {
+        let fragment: &'a str = input.fragment();
+        match parser(fragment) {
+            Ok((remain, output)) => Ok((input.slice(fragment.offset(remain)..), output)),
+            Err(err) => Err(err.map(|err| {
+                let offset = (err.input.as_ptr() as usize).wrapping_sub(fragment.as_ptr() as usize);
+                nom::error::Error::new(input.slice(offset.min(fragment.len())..), err.code)
+            })),
+        }
+    }
+}
+
+/// Wraps the output of `parser` into [`Spanned`] with the position and length of the consumed text.
+pub fn spanned<'a, O, F>(mut parser: F) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, Spanned<O>>
+    where F: FnMut(Span<'a>) -> IResult<Span<'a>, O> {
+    move |input: Span<'a>| {
+        let (remain, output) = parser(input)?;
+        let consumed = &input.fragment()[..input.fragment().offset(remain.fragment())];
+        let spanned = Spanned::new(output, input.location_line(), input.get_utf8_column(), consumed.chars().count());
+        Ok((remain, spanned))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{line_ending, space1};
+    use nom::multi::separated_list1;
+    use nom::sequence::preceded;
+
+    use crate::decimal;
+    use crate::span::{located, Span, spanned};
+
+    #[test]
+    fn check_spanned() {
+        let number = |input| spanned(located(decimal::<u32>))(input);
+        let mut parser = separated_list1(located(line_ending), separated_list1(located(space1), number));
+        let (remain, lines) = parser(Span::new("7 15\n300")).expect("lines parsed");
+        assert!(remain.is_empty());
+        let actual = lines.into_iter()
+            .flatten()
+            .map(|n| (*n.value(), n.line(), n.column(), n.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(7, 1, 1, 1), (15, 1, 3, 2), (300, 2, 1, 3)], actual);
+    }
+
+    #[test]
+    fn check_located_error() {
+        let input = Span::new("ab\nñc");
+        let mut parser = preceded(located(tag("ab\nñ")), located(tag("x")));
+        let nom::Err::Error(err) = parser(input).expect_err("x is missing") else { unreachable!() };
+        assert_eq!((2, 2), (err.input.location_line(), err.input.get_utf8_column()));
+        assert_eq!("c", *err.input.fragment());
+    }
+}
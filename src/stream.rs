@@ -0,0 +1,131 @@
+use std::io::BufRead;
+
+use nom::IResult;
+
+use crate::parse_error;
+
+/// Lines of a reader parsed one by one, memory is bounded by the longest line.
+///
+/// Empty lines are skipped. Iteration stops at the first read or parse error,
+/// which is kept in [`ParsedLines::error`].
+pub struct ParsedLines<B, P> {
+    reader: B,
+    parse: P,
+    buffer: String,
+    line: usize,
+    error: Option<String>,
+}
+
+impl<B, P> ParsedLines<B, P> {
+    pub fn new(reader: B, parse: P) -> Self {
+        Self { reader, parse, buffer: String::new(), line: 0, error: None }
+    }
+
+    /// Number of lines read so far.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Runs `solve` on the lines and returns its result, or the error that stopped them.
+    ///
+    /// `solve` only sees the lines before the first error, its result is dropped in that case.
+    pub fn solve<R>(&mut self, solve: impl FnOnce(&mut Self) -> R) -> Result<R, String> {
+        let result = solve(self);
+        match &self.error {
+            Some(err) => Err(err.clone()),
+            None => Ok(result),
+        }
+    }
+}
+
+impl<B, P, O> Iterator for ParsedLines<B, P>
+    where B: BufRead, P: for<'s> Fn(&'s str) -> IResult<&'s str, O> {
+    type Item = O;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.error.is_some() {
+            return None;
+        }
+        let line = loop {
+            self.buffer.clear();
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => return None,
+                Ok(_) => self.line += 1,
+                Err(err) => {
+                    self.error = Some(format!("‼️ Failed to read line {}: {err}", self.line + 1));
+                    return None;
+                }
+            }
+            let line = self.buffer.trim_end_matches(['\r', '\n']);
+            if !line.is_empty() {
+                break line;
+            }
+        };
+        match (self.parse)(line) {
+            Ok(("", output)) => Some(output),
+            Ok((remain, _)) => {
+                self.error = Some(format!("‼️ Line {} is not fully parsed. Remaining `{remain}`", self.line));
+                None
+            }
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+                self.error = Some(parse_error(line, self.line, &err));
+                None
+            }
+            Err(err) => {
+                self.error = Some(format!("‼️ Line {}: {err}", self.line));
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::numbers_list;
+    use crate::stream::ParsedLines;
+
+    #[test]
+    fn check_lines() {
+        let reader = Cursor::new("0 3 6\r\n1 3\n\n-4\nx 10\n");
+        let mut lines = ParsedLines::new(reader, numbers_list::<i64>);
+        assert_eq!(Some(vec![0, 3, 6]), lines.next());
+        assert_eq!(Some(vec![1, 3]), lines.next());
+        assert_eq!(Some(vec![-4]), lines.next());
+        assert_eq!(None, lines.next());
+        assert_eq!(5, lines.line());
+        assert!(lines.error().is_some_and(|err| err.contains("5:1") && err.ends_with("x 10\n^")), "{:?}", lines.error());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn check_solve() {
+        let mut lines = ParsedLines::new(Cursor::new("1 2\n3\n"), numbers_list::<u32>);
+        assert_eq!(Ok(6), lines.solve(|lines| lines.flatten().sum::<u32>()));
+
+        let mut seen = Vec::new();
+        let mut lines = ParsedLines::new(Cursor::new("1 2\nx\n3\n"), numbers_list::<u32>);
+        let result = lines.solve(|lines| lines.inspect(|line| seen.push(line.clone())).flatten().sum::<u32>());
+        assert!(result.is_err_and(|err| err.ends_with("x\n^")));
+        assert_eq!(vec![vec![1, 2]], seen);
+    }
+
+    #[test]
+    fn check_trailing_empty_lines() {
+        let mut lines = ParsedLines::new(Cursor::new("1 2\n3\n\n\n"), numbers_list::<u32>);
+        assert_eq!(vec![vec![1, 2], vec![3]], lines.by_ref().collect::<Vec<_>>());
+        assert_eq!(None, lines.error());
+    }
+
+    #[test]
+    fn check_remaining() {
+        let mut lines = ParsedLines::new(Cursor::new("1 2\n3 x\n"), numbers_list::<u32>);
+        assert_eq!(vec![vec![1, 2]], lines.by_ref().collect::<Vec<_>>());
+        assert!(lines.error().is_some_and(|err| err.contains("Remaining ` x`")), "{:?}", lines.error());
+    }
+}
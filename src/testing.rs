@@ -0,0 +1,239 @@
+use std::fmt::Debug;
+use std::ops::{Range, RangeInclusive};
+
+use nom::IResult;
+use num::{NumCast, PrimInt};
+
+use crate::matrix::Matrix;
+
+const CASES: usize = 256;
+const SEED: u64 = 0x2023_1225;
+
+/// Small deterministic xorshift generator, enough to drive [`Generator`]s.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// Uniform value in `range`, `range` must not be empty.
+    pub fn range(&mut self, range: Range<usize>) -> usize {
+        assert!(!range.is_empty(), "Empty range");
+        range.start + (self.next_u64() % (range.end - range.start) as u64) as usize
+    }
+
+    fn range_i128(&mut self, from: i128, to: i128) -> i128 {
+        let span = (to - from) as u128 + 1;
+        from + (self.next_u64() as u128 % span) as i128
+    }
+}
+
+/// Source of random values with a way to make a failing value smaller.
+pub trait Generator {
+    type Value;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// Simpler candidates for `value`, tried in order while shrinking a counterexample.
+    fn shrink(&self, _value: &Self::Value) -> Vec<Self::Value> {
+        Vec::new()
+    }
+}
+
+/// Checks that `parse(render(value)) == value` for generated values.
+///
+/// Trailing line endings left by `parse` are ignored. A failing value is shrunk
+/// before panicking, so the report shows a minimal counterexample.
+pub fn check_round_trip<G, R, P>(generator: &G, render: R, parse: P)
+    where
+        G: Generator,
+        G::Value: Debug + PartialEq,
+        R: Fn(&G::Value) -> String,
+        P: for<'s> Fn(&'s str) -> IResult<&'s str, G::Value>,
+{
+    let failure = |value: &G::Value| -> Option<String> {
+        let text = render(value);
+        match parse(&text) {
+            Ok((remain, parsed)) if remain.trim_end_matches(['\r', '\n']).is_empty() && parsed == *value => None,
+            Ok((remain, parsed)) => Some(format!("text:\n{text}\nparsed: {parsed:?}\nremaining: `{remain}`")),
+            Err(err) => Some(format!("text:\n{text}\nerror: {err}")),
+        }
+    };
+
+    let mut rng = Rng::new(SEED);
+    for case in 0..CASES {
+        let value = generator.generate(&mut rng);
+        if failure(&value).is_none() {
+            continue;
+        }
+        let mut value = value;
+        let mut shrinks = 0usize;
+        while let Some(smaller) = generator.shrink(&value).into_iter().find(|v| failure(v).is_some()) {
+            value = smaller;
+            shrinks += 1;
+        }
+        let report = failure(&value).unwrap();
+        panic!("Round trip failed on case #{case} after {shrinks} shrinks\nvalue: {value:?}\n{report}");
+    }
+}
+
+/// Lists of integers with a length from `len` and values from `values`.
+pub fn numbers<T: PrimInt>(len: Range<usize>, values: RangeInclusive<T>) -> Numbers<T> {
+    Numbers { len, values }
+}
+
+#[derive(Debug, Clone)]
+pub struct Numbers<T> {
+    len: Range<usize>,
+    values: RangeInclusive<T>,
+}
+
+impl<T: PrimInt> Numbers<T> {
+    /// Value of the range closest to zero, where numbers shrink to.
+    fn simplest(&self) -> T {
+        let (&from, &to) = (self.values.start(), self.values.end());
+        if from > T::zero() {
+            from
+        } else if to < T::zero() {
+            to
+        } else {
+            T::zero()
+        }
+    }
+}
+
+impl<T: PrimInt> Generator for Numbers<T> {
+    type Value = Vec<T>;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        let from: i128 = NumCast::from(*self.values.start()).unwrap();
+        let to: i128 = NumCast::from(*self.values.end()).unwrap();
+        (0..rng.range(self.len.clone()))
+            .map(|_| NumCast::from(rng.range_i128(from, to)).unwrap())
+            .collect()
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let mut candidates = Vec::new();
+        if value.len() > self.len.start {
+            for i in 0..value.len() {
+                let mut smaller = value.clone();
+                smaller.remove(i);
+                candidates.push(smaller);
+            }
+        }
+        let simplest = self.simplest();
+        let two = T::one() + T::one();
+        for (i, &number) in value.iter().enumerate() {
+            if number != simplest {
+                let mut smaller = value.clone();
+                smaller[i] = simplest + (number - simplest) / two;
+                candidates.push(smaller);
+            }
+        }
+        candidates
+    }
+}
+
+/// Grids with width and height from `size` and cells picked from `cells`.
+pub fn grids<T: Clone>(size: Range<usize>, cells: &[T]) -> Grids<T> {
+    assert!(!cells.is_empty(), "No cells to pick from");
+    Grids { size, cells: cells.to_vec() }
+}
+
+#[derive(Debug, Clone)]
+pub struct Grids<T> {
+    size: Range<usize>,
+    cells: Vec<T>,
+}
+
+impl<T: Clone + PartialEq> Generator for Grids<T> {
+    type Value = Matrix<T>;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        let width = rng.range(self.size.clone());
+        let height = rng.range(self.size.clone());
+        (0..height)
+            .map(|_| (0..width).map(|_| self.cells[rng.range(0..self.cells.len())].clone()).collect())
+            .collect::<Vec<Vec<T>>>()
+            .into()
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let rows = value.rows()
+            .map(|row| row.iter().cloned().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let (width, height) = (value.size().width(), value.size().height());
+        let mut candidates = Vec::new();
+        if height > self.size.start.max(1) {
+            candidates.push(rows[..height - 1].to_vec());
+        }
+        if width > self.size.start.max(1) {
+            candidates.push(rows.iter().map(|row| row[..width - 1].to_vec()).collect());
+        }
+        let simplest = &self.cells[0];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if cell != simplest {
+                    let mut simpler = rows.clone();
+                    simpler[y][x] = simplest.clone();
+                    candidates.push(simpler);
+                }
+            }
+        }
+        candidates.into_iter().map(Matrix::from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use nom::character::complete::{line_ending, one_of};
+    use nom::IResult;
+    use nom::multi::{many1, separated_list1};
+
+    use crate::matrix::Matrix;
+    use crate::numbers_list;
+    use crate::testing::{check_round_trip, grids, numbers};
+
+    fn render_grid(matrix: &Matrix<char>) -> String {
+        matrix.rows().map(|row| row.iter().collect::<String>()).join("\n")
+    }
+
+    #[test]
+    fn check_numbers() {
+        let render = |values: &Vec<i64>| values.iter().join(" ");
+        check_round_trip(&numbers(1..10, -1000..=1000), render, numbers_list::<i64>);
+    }
+
+    #[test]
+    fn check_grids() {
+        fn parse(input: &str) -> IResult<&str, Matrix<char>> {
+            let (input, rows) = separated_list1(line_ending, many1(one_of(".#O")))(input)?;
+            Ok((input, Matrix::from(rows)))
+        }
+        check_round_trip(&grids(1..8, &['.', '#', 'O']), render_grid, parse);
+    }
+
+    #[test]
+    #[should_panic(expected = "value: [-1]")]
+    fn check_shrinking() {
+        let render = |values: &Vec<i64>| values.iter().join(" ");
+        fn parse(input: &str) -> IResult<&str, Vec<i64>> {
+            let (input, values) = numbers_list::<u32>(input)?;
+            Ok((input, values.into_iter().map(i64::from).collect()))
+        }
+        check_round_trip(&numbers(1..10, -1000..=1000), render, parse);
+    }
+}